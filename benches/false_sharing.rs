@@ -0,0 +1,57 @@
+//! Measures how fast owners read the shared value while other threads
+//! keep adding and dropping owners, which writes to the counter. Compare
+//! the results with and without the `cache_padded` feature:
+//!
+//! ```text
+//! cargo bench --bench false_sharing
+//! cargo bench --bench false_sharing --features cache_padded
+//! ```
+
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use static_arc::StaticArc;
+
+const DURATION: Duration = Duration::from_secs(1);
+const CHURNERS: usize = 2;
+
+fn main() {
+    // small enough to share a cache line with the counter, unless padded
+    let [reader, churner] = StaticArc::new([1u64; 4]).unwrap();
+    let weak = churner.downgrade();
+    let stop = AtomicBool::new(false);
+
+    let (reads, drops) = thread::scope(|s| {
+        let churners: Vec<_> = (0..CHURNERS)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut drops = 0u64;
+                    while !stop.load(Ordering::Relaxed) {
+                        drop(black_box(weak.upgrade()));
+                        drops += 1;
+                    }
+                    drops
+                })
+            })
+            .collect();
+
+        let start = Instant::now();
+        let mut reads = 0u64;
+        while start.elapsed() < DURATION {
+            for _ in 0..1024 {
+                black_box(black_box(&reader).iter().sum::<u64>());
+            }
+            reads += 1024;
+        }
+        stop.store(true, Ordering::Relaxed);
+
+        let drops: u64 = churners.into_iter().map(|h| h.join().unwrap()).sum();
+        (reads, drops)
+    });
+
+    let padded = if cfg!(feature = "cache_padded") { "padded" } else { "unpadded" };
+    let secs = DURATION.as_secs_f64();
+    println!("{padded}: {:.1}M reads/s, {:.1}M drops/s", reads as f64 / secs / 1e6, drops as f64 / secs / 1e6);
+}
@@ -0,0 +1,42 @@
+//! Measures the round trip through a single owner: splitting a value
+//! into one owner, checking that it is unique, and reclaiming it.
+//!
+//! ```text
+//! cargo bench --bench single_owner
+//! ```
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use static_arc::StaticArc;
+
+const ITERATIONS: u32 = 10_000_000;
+
+fn bench(name: &str, mut f: impl FnMut(u64) -> u64) {
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        black_box(f(black_box(i.into())));
+    }
+    let elapsed = start.elapsed();
+    println!("{name}: {:.1} ns/iter", elapsed.as_nanos() as f64 / f64::from(ITERATIONS));
+}
+
+fn main() {
+    bench("new::<1> + try_into_inner", |i| {
+        let [owner] = StaticArc::new(i).unwrap();
+        owner.try_into_inner().unwrap()
+    });
+
+    bench("new::<1> + drop", |i| {
+        let [owner] = StaticArc::new(i).unwrap();
+        *owner
+    });
+
+    let [mut owner] = StaticArc::new(0).unwrap();
+    bench("is_unique", |_| u64::from(StaticArc::is_unique(&owner)));
+    bench("get_mut", |i| {
+        let value = StaticArc::get_mut(&mut owner).unwrap();
+        *value += i;
+        *value
+    });
+}
@@ -0,0 +1,16 @@
+//! Builds `static-arc` without `std`:
+//!
+//! ```text
+//! cargo build --manifest-path examples/no_std/Cargo.toml
+//! ```
+
+#![no_std]
+
+use static_arc::StaticArc;
+
+pub fn split(value: u32) -> Option<u32> {
+    let [a, b] = StaticArc::new(value)?;
+    let sum = *a + *b;
+    drop(a);
+    b.try_into_inner().map(|value| value + sum)
+}
@@ -28,17 +28,5 @@ fn main() {
 }
 
 fn recover(arc: StaticArc<SharedData>) -> HashMap<String, i32> {
-    let mut opt_arc = Some(arc);
-
-    loop {
-        let result = opt_arc
-            .take()
-            .unwrap()
-            .try_into_inner_recover();
-
-        match result {
-            Ok(shared) => return shared.data.into_inner().unwrap(),
-            Err(arc) => opt_arc = Some(arc),
-        }
-    }
+    arc.into_inner_spin().data.into_inner().unwrap()
 }
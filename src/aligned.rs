@@ -0,0 +1,99 @@
+//! Values aligned more strictly than their type requires, for
+//! [`StaticArc::new_aligned`](crate::StaticArc::new_aligned).
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+/// A value aligned to at least `ALIGN` bytes, e.g. for DMA buffers or
+/// SIMD loads, where `ALIGN` is a power of two up to 4096.
+///
+/// The alignment is part of the type, so allocations holding an
+/// `Aligned` value are laid out, and freed, with it.
+#[repr(C)]
+pub struct Aligned<T, const ALIGN: usize>
+where
+    Align<ALIGN>: Alignment,
+{
+    _align: [<Align<ALIGN> as Alignment>::Archetype; 0],
+    value: T,
+}
+
+/// An alignment supported by [`Aligned`].
+pub struct Align<const ALIGN: usize>;
+
+/// Implemented by the alignments [`Aligned`] supports.
+pub trait Alignment: sealed::Sealed {
+    #[doc(hidden)]
+    type Archetype;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! alignments {
+    ($($align:literal => $archetype:ident),* $(,)?) => {$(
+        #[doc(hidden)]
+        #[repr(align($align))]
+        pub struct $archetype;
+
+        impl sealed::Sealed for Align<$align> {}
+
+        impl Alignment for Align<$align> {
+            type Archetype = $archetype;
+        }
+    )*};
+}
+
+alignments! {
+    1 => Align1, 2 => Align2, 4 => Align4, 8 => Align8,
+    16 => Align16, 32 => Align32, 64 => Align64, 128 => Align128,
+    256 => Align256, 512 => Align512, 1024 => Align1024,
+    2048 => Align2048, 4096 => Align4096,
+}
+
+impl<T, const ALIGN: usize> Aligned<T, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Aligned { _align: [], value }
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, const ALIGN: usize> Deref for Aligned<T, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, const ALIGN: usize> DerefMut for Aligned<T, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Debug, const ALIGN: usize> fmt::Debug for Aligned<T, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
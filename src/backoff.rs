@@ -0,0 +1,73 @@
+//! Exponential backoff for the loops waiting on other owners,
+//! along the lines of `crossbeam_utils::Backoff`.
+
+use crate::sync;
+
+/// Steps during which the backoff only spins, doubling
+/// the number of spins each time.
+const SPIN_LIMIT: u32 = 6;
+
+/// Steps after which the backoff stops growing.
+const YIELD_LIMIT: u32 = 10;
+
+pub(crate) struct Backoff {
+    step: u32,
+}
+
+impl Backoff {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self { step: 0 }
+    }
+
+    /// Backs off while waiting on another thread, yielding
+    /// to the scheduler once spinning has gone on for long
+    /// enough. Without `std`, it keeps spinning instead.
+    #[inline]
+    pub(crate) fn snooze(&mut self) {
+        self.snooze_with(sync::spin_loop, sync::yield_now);
+    }
+
+    /// Like [`Backoff::snooze`], with `spin` called for each spin,
+    /// and `yield_now` called to yield, so tests can count them.
+    pub(crate) fn snooze_with(&mut self, mut spin: impl FnMut(), yield_now: impl FnOnce()) {
+        if self.step <= SPIN_LIMIT {
+            for _ in 0..1 << self.step {
+                spin();
+            }
+        } else {
+            yield_now();
+        }
+
+        if self.step <= YIELD_LIMIT {
+            self.step += 1;
+        }
+    }
+
+    /// Whether the backoff has reached its final step.
+    #[cfg(all(test, not(loom)))]
+    pub(crate) fn is_completed(&self) -> bool {
+        self.step > YIELD_LIMIT
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_escalates() {
+        let mut backoff = Backoff::new();
+        let mut spins = [0; YIELD_LIMIT as usize + 2];
+        let mut yields = 0;
+
+        for step_spins in &mut spins {
+            backoff.snooze_with(|| *step_spins += 1, || yields += 1);
+        }
+
+        // spinning twice as long at each step, then only yielding
+        assert!(backoff.is_completed());
+        assert_eq!(spins, [1, 2, 4, 8, 16, 32, 64, 0, 0, 0, 0, 0]);
+        assert_eq!(yields, YIELD_LIMIT - SPIN_LIMIT + 1);
+    }
+}
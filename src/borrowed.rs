@@ -0,0 +1,93 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::num::NonZeroUsize;
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+use crate::{assert_owners, StaticArc, StaticArcInner, StaticMemory};
+
+/// An owner of a value whose control block lives in storage borrowed
+/// from the caller, rather than on the heap, for targets without one.
+///
+/// It behaves like a [`StaticArc`] that can't be downgraded: the last
+/// owner to be dropped drops the value, but the storage is left alone,
+/// and may be reused once every owner is gone.
+///
+/// Returned by [`StaticArc::from_storage`].
+pub struct StaticArcBorrowed<'a, T> {
+    owner: StaticArc<T, StaticMemory>,
+    storage: PhantomData<&'a mut StaticArcInner<T, StaticMemory>>,
+}
+
+impl<T> StaticArc<T, StaticMemory> {
+    /// Moves `value` into `storage`, and returns its `N` owners,
+    /// which borrow the storage until they are all dropped:
+    ///
+    /// ```
+    /// use std::mem::MaybeUninit;
+    /// use static_arc::StaticArc;
+    ///
+    /// let mut storage = MaybeUninit::uninit();
+    /// let [p1, p2] = StaticArc::from_storage::<2>(&mut storage, [1, 2, 3]);
+    /// assert_eq!(*p1, *p2);
+    /// drop((p1, p2));
+    ///
+    /// // every owner is gone, so the storage can be reused
+    /// let [p1] = StaticArc::from_storage::<1>(&mut storage, [4, 5, 6]);
+    /// assert_eq!(p1.try_into_inner(), Some([4, 5, 6]));
+    /// ```
+    pub fn from_storage<const N: usize>(
+        storage: &mut MaybeUninit<StaticArcInner<T, StaticMemory>>,
+        value: T,
+    ) -> [StaticArcBorrowed<'_, T>; N] {
+        assert_owners::<N>();
+
+        let inner = storage.as_mut_ptr();
+
+        // SAFETY: `inner` points at storage that fits a `StaticArcInner`,
+        // which is borrowed for as long as the owners are alive
+        let owners: [Self; N] = unsafe {
+            StaticArcInner::init_uncounted(inner, N, StaticMemory);
+            core::ptr::addr_of_mut!((*inner).value)
+                .write(UnsafeCell::new(ManuallyDrop::new(value)));
+            Self::from_inner(NonNull::new_unchecked(inner))
+        };
+
+        owners.map(|owner| StaticArcBorrowed { owner, storage: PhantomData })
+    }
+}
+
+impl<T> StaticArcBorrowed<'_, T> {
+    /// Like [`StaticArc::live`].
+    #[inline]
+    pub fn live(&self) -> NonZeroUsize {
+        self.owner.live()
+    }
+
+    /// Like [`StaticArc::try_into_inner`].
+    #[must_use = "ignoring the result drops the value, if it was reclaimed"]
+    #[inline]
+    pub fn try_into_inner(self) -> Option<T> {
+        self.owner.try_into_inner()
+    }
+}
+
+impl<T> Deref for StaticArcBorrowed<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.owner
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for StaticArcBorrowed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticArcBorrowed")
+            .field("live", &self.live())
+            .field("value", &**self)
+            .finish()
+    }
+}
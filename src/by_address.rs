@@ -0,0 +1,77 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::{Allocator, Global, StaticArc};
+
+/// An owner compared and hashed by the allocation it belongs to,
+/// rather than by the shared value, like the `by_address` crate does.
+///
+/// Sibling owners are equal to each other, while owners of different
+/// allocations never are, even if their values compare equal.
+///
+/// Returned by [`StaticArc::by_address`].
+pub struct ByAddress<T: ?Sized, A: Allocator = Global>(pub StaticArc<T, A>);
+
+impl<T: ?Sized, A: Allocator> ByAddress<T, A> {
+    /// Returns the wrapped owner.
+    #[inline]
+    pub fn into_inner(self) -> StaticArc<T, A> {
+        self.0
+    }
+}
+
+impl<T: ?Sized, A: Allocator> PartialEq for ByAddress<T, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        StaticArc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Eq for ByAddress<T, A> {}
+
+impl<T: ?Sized, A: Allocator> PartialOrd for ByAddress<T, A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Ord for ByAddress<T, A> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.addr().cmp(&other.0.addr())
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Hash for ByAddress<T, A> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.addr().hash(state);
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Deref for ByAddress<T, A> {
+    type Target = StaticArc<T, A>;
+
+    #[inline]
+    fn deref(&self) -> &StaticArc<T, A> {
+        &self.0
+    }
+}
+
+impl<T: ?Sized, A: Allocator> From<StaticArc<T, A>> for ByAddress<T, A> {
+    #[inline]
+    fn from(owner: StaticArc<T, A>) -> Self {
+        ByAddress(owner)
+    }
+}
+
+impl<T: ?Sized, A: Allocator> fmt::Debug for ByAddress<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ByAddress")
+            .field(&self.0.as_ptr().cast::<()>())
+            .finish()
+    }
+}
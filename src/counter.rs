@@ -0,0 +1,217 @@
+//! Counters of owners, shared by [`StaticArc`](crate::StaticArc) and
+//! [`StaticRc`](crate::StaticRc), so that both follow the same logic.
+
+use core::cell::Cell;
+
+use crate::sync::{self, AtomicUsize, Ordering};
+
+// `pub` in a private module, as it bounds the counter of the
+// public `StaticArcInner`, but can't be named outside of the crate
+pub trait Counter {
+    fn new(count: usize) -> Self;
+
+    /// Returns the number of owners; observing a count of 1 means
+    /// every access of the dropped owners happens before ours.
+    fn get(&self) -> usize;
+
+    /// Removes an owner, returning the previous count; if it was 1,
+    /// every access of the other owners happens before ours.
+    fn decrement(&self) -> usize;
+
+    /// Brings the count from 1 to 0, if we are the only owner left,
+    /// such that every access of the other owners happens before ours.
+    ///
+    /// Otherwise, returns the count which made this fail.
+    fn try_reclaim(&self) -> Result<(), usize>;
+}
+
+impl Counter for AtomicUsize {
+    #[inline]
+    fn new(count: usize) -> Self {
+        AtomicUsize::new(count)
+    }
+
+    #[inline]
+    fn get(&self) -> usize {
+        // acquire, so that if we observe a count of 1, all accesses
+        // to the value by the other (now dropped) owners happen before
+        // whatever the caller decides to do with it
+        self.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    fn decrement(&self) -> usize {
+        // release, so that any accesses to the value through this owner
+        // happen before the value is dropped or reclaimed by another one
+        let previous = self.fetch_sub(1, Ordering::Release);
+        debug_assert!(previous >= 1, "owner dropped with a count of 0");
+
+        // as in `std::sync::Arc`, the fence synchronizes with the release
+        // decrements of the other owners, without paying for acquire
+        // ordering on every decrement
+        if previous == 1 {
+            sync::fence(Ordering::Acquire);
+        }
+
+        previous
+    }
+
+    #[inline]
+    fn try_reclaim(&self) -> Result<(), usize> {
+        self.compare_exchange(1, 0, Ordering::Relaxed, Ordering::Relaxed)?;
+
+        // synchronize with the release decrements of dropped owners, like
+        // when dropping the last owner, so that anything they wrote to the
+        // value is visible before we read it; the compare exchange above
+        // is relaxed, so this fence is what makes reclaiming sound
+        sync::fence(Ordering::Acquire);
+        Ok(())
+    }
+}
+
+// owners can't be shared between threads, so there's nothing to synchronize
+impl Counter for Cell<usize> {
+    #[inline]
+    fn new(count: usize) -> Self {
+        Cell::new(count)
+    }
+
+    #[inline]
+    fn get(&self) -> usize {
+        Cell::get(self)
+    }
+
+    #[inline]
+    fn decrement(&self) -> usize {
+        let previous = self.get();
+        debug_assert!(previous >= 1, "owner dropped with a count of 0");
+        self.set(previous - 1);
+        previous
+    }
+
+    #[inline]
+    fn try_reclaim(&self) -> Result<(), usize> {
+        match self.get() {
+            1 => {
+                self.set(0);
+                Ok(())
+            },
+            live => Err(live),
+        }
+    }
+}
+
+#[cfg(feature = "cache_padded")]
+impl<C: Counter> Counter for crate::padded::CachePadded<C> {
+    #[inline]
+    fn new(count: usize) -> Self {
+        crate::padded::CachePadded::new(C::new(count))
+    }
+
+    #[inline]
+    fn get(&self) -> usize {
+        (**self).get()
+    }
+
+    #[inline]
+    fn decrement(&self) -> usize {
+        (**self).decrement()
+    }
+
+    #[inline]
+    fn try_reclaim(&self) -> Result<(), usize> {
+        (**self).try_reclaim()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    // the same behavior is expected from every counter
+    macro_rules! counter_suite {
+        ($name:ident, $counter:ty) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn test_decrement() {
+                    let counter = <$counter as Counter>::new(3);
+                    assert_eq!(counter.get(), 3);
+                    assert_eq!(counter.decrement(), 3);
+                    assert_eq!(counter.decrement(), 2);
+                    assert_eq!(counter.get(), 1);
+                    assert_eq!(counter.decrement(), 1);
+                    assert_eq!(counter.get(), 0);
+                }
+
+                #[test]
+                fn test_try_reclaim() {
+                    let counter = <$counter as Counter>::new(2);
+                    assert_eq!(counter.try_reclaim(), Err(2));
+                    assert_eq!(counter.get(), 2);
+                    counter.decrement();
+                    assert_eq!(counter.try_reclaim(), Ok(()));
+                    assert_eq!(counter.get(), 0);
+                    assert_eq!(counter.try_reclaim(), Err(0));
+                }
+            }
+        };
+    }
+
+    counter_suite!(atomic, AtomicUsize);
+    counter_suite!(cell, Cell<usize>);
+    #[cfg(feature = "cache_padded")]
+    counter_suite!(cache_padded, crate::padded::CachePadded<AtomicUsize>);
+
+    // and from the owners built on top of them
+    macro_rules! owner_suite {
+        ($name:ident, $owner:ident) => {
+            mod $name {
+                use std::prelude::rust_2021::*;
+
+                use crate::$owner;
+
+                #[test]
+                fn test_live() {
+                    let [p1, p2, p3] = $owner::new(5).unwrap();
+                    assert!($owner::ptr_eq(&p1, &p3));
+                    assert_eq!(p2.live().get(), 3);
+                    drop(p3);
+                    assert_eq!(p1.live().get(), 2);
+                    assert!($owner::<i32>::new::<0>(5).is_none());
+                }
+
+                #[test]
+                fn test_reclaim() {
+                    let [p1, p2] = $owner::new(String::from("owner")).unwrap();
+                    let p1 = p1.try_into_inner_recover().unwrap_err();
+                    drop(p2);
+                    assert_eq!(p1.try_into_inner_recover().ok().unwrap(), "owner");
+                }
+
+                #[test]
+                fn test_get_mut() {
+                    let [mut p1, p2] = $owner::new(vec![1]).unwrap();
+                    assert!($owner::get_mut(&mut p1).is_none());
+                    drop(p2);
+                    $owner::get_mut(&mut p1).unwrap().push(2);
+                    assert_eq!(*p1, [1, 2]);
+                }
+
+                #[test]
+                fn test_drop() {
+                    let value = std::rc::Rc::new(());
+                    let [p1, p2] = $owner::new(std::rc::Rc::clone(&value)).unwrap();
+                    drop(p1);
+                    assert_eq!(std::rc::Rc::strong_count(&value), 2);
+                    drop(p2);
+                    assert_eq!(std::rc::Rc::strong_count(&value), 1);
+                }
+            }
+        };
+    }
+
+    owner_suite!(static_arc, StaticArc);
+    owner_suite!(static_rc, StaticRc);
+}
@@ -0,0 +1,45 @@
+use core::fmt;
+
+use crate::{Allocator, Global, StaticArc};
+
+/// The error returned by [`StaticArc::try_into_inner_err`] when other
+/// owners are still alive, which gives the owner back.
+pub struct ReclaimError<T, A: Allocator = Global> {
+    owner: StaticArc<T, A>,
+    live: usize,
+}
+
+impl<T, A: Allocator> ReclaimError<T, A> {
+    pub(crate) fn new(owner: StaticArc<T, A>, live: usize) -> Self {
+        ReclaimError { owner, live }
+    }
+
+    /// Returns the owner which failed to reclaim the value.
+    #[inline]
+    pub fn into_owner(self) -> StaticArc<T, A> {
+        self.owner
+    }
+
+    /// Returns the number of owners observed when reclaiming the
+    /// value failed, which may be stale by now.
+    #[inline]
+    pub fn live(&self) -> usize {
+        self.live
+    }
+}
+
+impl<T, A: Allocator> fmt::Debug for ReclaimError<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReclaimError")
+            .field("live", &self.live)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, A: Allocator> fmt::Display for ReclaimError<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot reclaim: {} owners still live", self.live)
+    }
+}
+
+impl<T, A: Allocator> core::error::Error for ReclaimError<T, A> {}
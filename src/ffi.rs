@@ -0,0 +1,70 @@
+//! Reference counting across an FFI boundary.
+//!
+//! A value shared by [`StaticArc`] owners lives in a `repr(C)` allocation,
+//! right after a header starting with the counter of owners. The layout
+//! of the header isn't stable, so rather than touching the counter, C code
+//! is handed the functions below, instantiated for the type of the value:
+//!
+//! ```
+//! use core::ffi::c_void;
+//! use static_arc::{ffi, StaticArc};
+//!
+//! // these can be passed to C, along with the pointer
+//! let addref: unsafe extern "C" fn(*const c_void) = ffi::static_arc_addref::<String>;
+//! let release: unsafe extern "C" fn(*const c_void) = ffi::static_arc_release::<String>;
+//!
+//! let [owner] = StaticArc::new(String::from("shared")).unwrap();
+//! let ptr = owner.into_raw().cast::<c_void>();
+//!
+//! unsafe {
+//!     addref(ptr);
+//!     release(ptr);
+//!     release(ptr);
+//! }
+//! ```
+
+use core::ffi::c_void;
+use core::mem::ManuallyDrop;
+
+use crate::sync::Ordering;
+use crate::{abort, StaticArc, MAX_REFCOUNT};
+
+/// Adds an owner of the value pointed to by `ptr`, which must
+/// be released with [`static_arc_release`].
+///
+/// # Safety
+///
+/// `ptr` must point at a value shared by live owners of type
+/// `StaticArc<T>`, as returned by [`StaticArc::into_raw`], and
+/// one of those owners must be kept alive during this call.
+pub unsafe extern "C" fn static_arc_addref<T>(ptr: *const c_void) {
+    // SAFETY: upheld by the caller; we don't release
+    // the owner we borrow
+    let owner = ManuallyDrop::new(unsafe { StaticArc::<T>::from_raw_unchecked(ptr.cast()) });
+
+    // the added owner is released through `from_raw`
+    owner.arc().raw_given();
+
+    // like cloning an `std::sync::Arc`, adding owners through
+    // an existing one doesn't need to synchronize with anything
+    let old = owner.arc().counter.fetch_add(1, Ordering::Relaxed);
+
+    if old > MAX_REFCOUNT {
+        abort();
+    }
+}
+
+/// Releases an owner of the value pointed to by `ptr`, dropping
+/// the value if it was the last one. Panics while dropping it abort,
+/// since they can't unwind into C.
+///
+/// # Safety
+///
+/// `ptr` must have been returned by [`StaticArc::into_raw`] for a
+/// `StaticArc<T>`, and the owner it stands for (either that one,
+/// or one added with [`static_arc_addref`]) may only be released
+/// once.
+pub unsafe extern "C" fn static_arc_release<T>(ptr: *const c_void) {
+    // SAFETY: upheld by the caller
+    drop(unsafe { StaticArc::<T>::from_raw(ptr.cast()) });
+}
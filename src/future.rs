@@ -0,0 +1,59 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::{Allocator, Global, StaticArc};
+
+/// A future that resolves into the value shared by a [`StaticArc`],
+/// once every other owner has been dropped.
+///
+/// Returned by [`StaticArc::into_inner_async`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct IntoInner<T, A: Allocator = Global> {
+    owner: Option<StaticArc<T, A>>,
+    /// The key of our waker, once registered.
+    key: Option<usize>,
+}
+
+impl<T, A: Allocator> IntoInner<T, A> {
+    pub(crate) fn new(owner: StaticArc<T, A>) -> Self {
+        IntoInner { owner: Some(owner), key: None }
+    }
+}
+
+impl<T, A: Allocator> Future for IntoInner<T, A> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let owner = self
+            .owner
+            .take()
+            .expect("`IntoInner` polled after completion");
+
+        // register before checking the count, so we can't miss the wake
+        // up from the second to last owner; this replaces our waker from
+        // the last poll, if it hasn't been woken since
+        let key = owner.arc().waiters.register(self.key, cx.waker());
+        self.key = Some(key);
+
+        // once reclaimed, our waker is freed along with the allocation,
+        // as it can't be woken anymore
+        match owner.try_into_inner_recover() {
+            Ok(value) => Poll::Ready(value),
+            Err(owner) => {
+                self.owner = Some(owner);
+                Poll::Pending
+            },
+        }
+    }
+}
+
+impl<T, A: Allocator> Drop for IntoInner<T, A> {
+    fn drop(&mut self) {
+        // a pending future takes its waker out, before dropping its owner
+        if let (Some(owner), Some(key)) = (&self.owner, self.key) {
+            owner.arc().waiters.deregister(key);
+        }
+    }
+}
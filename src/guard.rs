@@ -0,0 +1,45 @@
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use crate::{Allocator, Global, StaticArc};
+
+/// Mutable access to the value of a unique owner, for as long as
+/// the guard is alive.
+///
+/// Returned by [`StaticArc::lock_unique`].
+pub struct UniqueGuard<'a, T: ?Sized, A: Allocator = Global> {
+    pub(crate) owner: &'a mut StaticArc<T, A>,
+}
+
+impl<T: ?Sized, A: Allocator> Deref for UniqueGuard<'_, T, A> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.owner
+    }
+}
+
+impl<T: ?Sized, A: Allocator> DerefMut for UniqueGuard<'_, T, A> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the guard was created for the only owner left, with
+        // no weak references, and it borrows that owner mutably
+        unsafe { &mut *self.owner.value_ptr() }
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Drop for UniqueGuard<'_, T, A> {
+    #[inline]
+    fn drop(&mut self) {
+        // with the `debug_checks` feature, catch mutations
+        // that corrupted the control block
+        self.owner.arc().check();
+    }
+}
+
+impl<T: ?Sized + fmt::Debug, A: Allocator> fmt::Debug for UniqueGuard<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("UniqueGuard").field(&&**self).finish()
+    }
+}
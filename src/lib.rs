@@ -1,20 +1,635 @@
-use std::ptr::NonNull;
-use std::num::NonZeroUsize;
-use std::ops::{Deref, Drop};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::mem::{self, MaybeUninit, ManuallyDrop};
+#![no_std]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#![cfg_attr(feature = "coerce_unsized", feature(coerce_unsized, unsize))]
 
-#[derive(Debug)]
-pub struct StaticArc<T> {
-    inner: NonNull<StaticArcInner<T>>,
+extern crate alloc;
+
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
+use core::ptr::NonNull;
+use core::num::NonZeroUsize;
+use core::borrow::Borrow;
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, Drop};
+use core::panic::{RefUnwindSafe, UnwindSafe};
+use core::pin::Pin;
+#[cfg(feature = "async")]
+use core::task::{Context, Poll};
+use core::mem::{self, MaybeUninit, ManuallyDrop};
+#[cfg(feature = "park")]
+use core::time::Duration;
+
+use alloc::boxed::Box;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use allocator_api2::alloc::{handle_alloc_error, Layout};
+
+pub use allocator_api2::alloc::{AllocError, Allocator, Global};
+
+mod aligned;
+mod backoff;
+mod borrowed;
+mod by_address;
+mod counter;
+mod error;
+pub mod ffi;
+#[cfg(feature = "async")]
+mod future;
+mod guard;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod owner_set;
+#[cfg(feature = "park")]
+mod parker;
+#[cfg(feature = "cache_padded")]
+mod padded;
+mod projected;
+mod rc;
+mod slot;
+mod statics;
+mod sync;
+mod trace;
+#[cfg(feature = "async")]
+mod waiters;
+mod weak;
+
+use counter::Counter;
+#[cfg(feature = "park")]
+use parker::{Parker, Waiter};
+#[cfg(feature = "async")]
+use waiters::Waiters;
+use sync::{AtomicPtr, AtomicU8, AtomicUsize, Ordering};
+use trace::trace;
+
+pub use aligned::{Align, Aligned, Alignment};
+pub use borrowed::StaticArcBorrowed;
+pub use by_address::ByAddress;
+pub use error::ReclaimError;
+pub use guard::UniqueGuard;
+#[cfg(feature = "metrics")]
+pub use metrics::live_allocations;
+#[cfg(feature = "async")]
+pub use future::IntoInner;
+pub use owner_set::OwnerSet;
+pub use projected::StaticArcProjected;
+pub use rc::StaticRc;
+pub use slot::AllocSlot;
+pub use statics::StaticMemory;
+pub use weak::StaticWeak;
+
+/// Soft limit on the number of references, past which we abort,
+/// as in `std::sync::Arc`.
+const MAX_REFCOUNT: usize = isize::MAX as usize;
+
+/// States of [`StaticArc::init_once`].
+const INIT_NONE: u8 = 0;
+const INIT_RUNNING: u8 = 1;
+const INIT_DONE: u8 = 2;
+
+/// Stored in the header of live allocations, with the `debug_checks`
+/// feature.
+#[cfg(feature = "debug_checks")]
+const MAGIC: usize = 0x57a7_1c0d;
+
+/// Whether an allocation may be split into `n` owners: at least one,
+/// so that someone frees it, and no more than can be counted.
+///
+/// Every constructor and split goes through this check.
+#[inline]
+const fn valid_owners(n: usize) -> bool {
+    matches!(n, 1..=MAX_REFCOUNT)
+}
+
+/// Rejects `N` at compile time unless [`valid_owners`] accepts it, for
+/// the constructors which can't fail otherwise.
+#[inline]
+const fn assert_owners<const N: usize>() {
+    const { assert!(valid_owners(N), "a `StaticArc` must have at least one owner") };
+}
+
+/// Like [`assert_owners`], for splitting an allocation
+/// into two groups of `L` and `R` owners.
+#[inline]
+const fn assert_split<const L: usize, const R: usize>() {
+    const {
+        assert!(
+            matches!(L.checked_add(R), Some(total) if valid_owners(total)),
+            "a `StaticArc` must have at least one owner"
+        )
+    };
+}
+
+#[cold]
+fn abort() -> ! {
+    #[cfg(feature = "std")]
+    std::process::abort();
+
+    // without `std`, panicking while panicking aborts
+    #[cfg(not(feature = "std"))]
+    {
+        struct Abort;
+
+        impl Drop for Abort {
+            fn drop(&mut self) {
+                panic!("aborting");
+            }
+        }
+
+        let _abort = Abort;
+        panic!("reference count overflow");
+    }
+}
+
+/// Drops the value it points to when dropped, so that the value
+/// is dropped even when unwinding.
+struct DropInPlace<T: ?Sized>(*mut T);
+
+impl<T: ?Sized> Drop for DropInPlace<T> {
+    fn drop(&mut self) {
+        // SAFETY: guaranteed by whoever constructed us
+        unsafe { core::ptr::drop_in_place(self.0) }
+    }
+}
+
+/// Releases the weak reference to an allocation it holds when dropped,
+/// so that the allocation is freed even when unwinding.
+struct ReleaseWeak<T: ?Sized, A: Allocator, C: Counter>(NonNull<StaticArcInner<T, A, C>>);
+
+impl<T: ?Sized, A: Allocator, C: Counter> Drop for ReleaseWeak<T, A, C> {
+    fn drop(&mut self) {
+        // SAFETY: guaranteed by whoever constructed us
+        unsafe { StaticArcInner::release_weak(self.0) }
+    }
+}
+
+/// Replaces the address of a (possibly wide) pointer, keeping its metadata.
+fn set_ptr_addr<T: ?Sized>(mut ptr: *mut T, addr: *mut u8) -> *mut T {
+    // SAFETY: the address always comes first in the representation of
+    // wide pointers, which is how `std` has implemented this as well
+    unsafe {
+        *core::ptr::addr_of_mut!(ptr).cast::<*mut u8>() = addr;
+    }
+    ptr
+}
+
+/// A value shared like with a `std::sync::Arc`, except that every
+/// owner is handed out at once, e.g. by [`StaticArc::new`], so their
+/// number is known a priori.
+///
+/// # Associated functions
+///
+/// Owners dereference to the shared value, so like with `Arc`, most
+/// functions acting on the owner itself are associated functions,
+/// called as `StaticArc::get_mut(&mut owner)` rather than
+/// `owner.get_mut()`. That way, they don't shadow methods of `T`
+/// reached through `Deref`, like `Cell::replace` would be by
+/// [`StaticArc::replace`].
+pub struct StaticArc<T: ?Sized, A: Allocator = Global> {
+    inner: NonNull<StaticArcInner<T, A>>,
+}
+
+/// Callback registered with [`StaticArc::on_last_drop`], which has
+/// already captured a pointer to the shared value.
+///
+/// It doesn't mention `T`, so the header of `StaticArcInner` stays the
+/// same when unsizing the value.
+type OnLastDrop = Box<dyn FnOnce() + Send>;
+
+/// Pointer to the shared value, captured by [`OnLastDrop`] callbacks.
+struct ValuePtr<T: ?Sized>(*mut T);
+
+// SAFETY: the callback only runs in place of the drop of the value,
+// which only happens on another thread if owners are `Send`
+unsafe impl<T: ?Sized> Send for ValuePtr<T> {}
+
+impl<T: ?Sized> ValuePtr<T> {
+    /// # Safety
+    ///
+    /// The value must be alive, and not otherwise borrowed for `'a`.
+    unsafe fn into_mut<'a>(self) -> &'a mut T {
+        // SAFETY: upheld by the caller
+        unsafe { &mut *self.0 }
+    }
+}
+
+/// The counter of owners, which is written to by every owner being
+/// dropped; with the `cache_padded` feature it gets a cache line of
+/// its own, so it doesn't slow down reads of the shared value.
+#[cfg(feature = "cache_padded")]
+type StrongCount = padded::CachePadded<AtomicUsize>;
+
+#[cfg(not(feature = "cache_padded"))]
+type StrongCount = AtomicUsize;
+
+// `repr(C)`, so we can compute the layout of allocations holding
+// unsized values, which must be the last field; zero-sized values
+// take no space, so they only need the header to be allocated; the
+// counter comes first, as documented in the `ffi` module
+/// The control block of a shared allocation: the counters of its
+/// owners and weak references, followed by the shared value.
+///
+/// It is only exposed to place control blocks in static memory, with
+/// [`StaticArcInner::new`] and [`StaticArc::from_static_inner`].
+///
+/// `C` is the counter of owners, which is atomic for [`StaticArc`] and a
+/// `Cell` for [`StaticRc`], so that both share the same logic.
+#[repr(C)]
+pub struct StaticArcInner<T: ?Sized, A: Allocator = Global, C = StrongCount> {
+    counter: C,
+    // the number of owners the allocation was last split into,
+    // when created or by `resplit` and `split_at`
+    original: AtomicUsize,
+    // owners turned into raw pointers or parts, which may
+    // be reconstructed
+    #[cfg(feature = "checked_raw")]
+    raw: AtomicUsize,
+    // all strong owners collectively hold one weak reference,
+    // which is released when the value is dropped
+    weak: AtomicUsize,
+    // state of `StaticArc::init_once`
+    init: AtomicU8,
+    // whether the allocation counts towards `live_allocations`,
+    // which control blocks in `static`s don't
+    #[cfg(feature = "metrics")]
+    counted: bool,
+    // overwritten when freeing the allocation, to catch uses after free
+    #[cfg(feature = "debug_checks")]
+    magic: AtomicUsize,
+    // wakers of the tasks waiting for a single owner to be left
+    #[cfg(feature = "async")]
+    waiters: Waiters,
+    // the thread parked in `StaticArc::into_inner_wait`
+    #[cfg(feature = "park")]
+    parker: Parker,
+    // boxed `OnLastDrop`, or null
+    on_last_drop: AtomicPtr<OnLastDrop>,
+    // moved out by whoever frees the allocation
+    alloc: A,
+    value: UnsafeCell<ManuallyDrop<T>>,
+}
+
+impl<T: ?Sized, A: Allocator, C: Counter> StaticArcInner<T, A, C> {
+    /// Returns the layout of an allocation holding a value with the given
+    /// layout, along with the offset of the value within it.
+    fn layout_for(value: Layout) -> (Layout, usize) {
+        // with `repr(C)`, the offsets of the fields preceding `value`
+        // don't depend on `T`, and `()` doesn't add any trailing padding
+        let header = Layout::from_size_align(
+            mem::offset_of!(StaticArcInner<(), A>, value),
+            mem::align_of::<StaticArcInner<(), A>>(),
+        );
+
+        let (layout, offset) = header
+            .and_then(|header| header.extend(value))
+            .expect("value too large to be shared");
+
+        (layout.pad_to_align(), offset)
+    }
+
+    /// Initializes every field of a fresh allocation, except for the value,
+    /// counting it towards `live_allocations` with the `metrics` feature.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point at an allocation that fits a `StaticArcInner<T, A, C>`.
+    unsafe fn init(this: *mut Self, count: usize, alloc: A) {
+        // SAFETY: upheld by the caller
+        unsafe { Self::init_uncounted(this, count, alloc) };
+
+        #[cfg(feature = "metrics")]
+        {
+            // SAFETY: the caller guarantees `this` can be written to
+            unsafe { (*this).counted = true };
+            metrics::allocated();
+        }
+    }
+
+    /// Like [`StaticArcInner::init`], for storage which didn't come from
+    /// an allocator, and so doesn't count towards `live_allocations`.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point at storage that fits a `StaticArcInner<T, A, C>`.
+    unsafe fn init_uncounted(this: *mut Self, count: usize, alloc: A) {
+        // SAFETY: the caller guarantees `this` can be written to
+        unsafe {
+            core::ptr::addr_of_mut!((*this).counter).write(C::new(count));
+            core::ptr::addr_of_mut!((*this).original).write(AtomicUsize::new(count));
+            #[cfg(feature = "checked_raw")]
+            core::ptr::addr_of_mut!((*this).raw).write(AtomicUsize::new(0));
+            core::ptr::addr_of_mut!((*this).weak).write(AtomicUsize::new(1));
+            core::ptr::addr_of_mut!((*this).init).write(AtomicU8::new(INIT_NONE));
+            #[cfg(feature = "metrics")]
+            core::ptr::addr_of_mut!((*this).counted).write(false);
+            #[cfg(feature = "debug_checks")]
+            core::ptr::addr_of_mut!((*this).magic).write(AtomicUsize::new(MAGIC));
+            #[cfg(feature = "async")]
+            core::ptr::addr_of_mut!((*this).waiters).write(Waiters::new());
+            #[cfg(feature = "park")]
+            core::ptr::addr_of_mut!((*this).parker).write(Parker::new());
+            core::ptr::addr_of_mut!((*this).on_last_drop).write(AtomicPtr::new(core::ptr::null_mut()));
+            core::ptr::addr_of_mut!((*this).alloc).write(alloc);
+        }
+
+        trace!(
+            owners = count,
+            ty = core::any::type_name::<T>(),
+            addr = ?this.cast::<()>(),
+            "shared allocation created",
+        );
+    }
+
+    /// Panics unless the header looks like that of a live allocation,
+    /// with the `debug_checks` feature; does nothing otherwise.
+    #[inline]
+    fn check(&self) {
+        #[cfg(feature = "debug_checks")]
+        {
+            let magic = self.magic.load(Ordering::Relaxed);
+            assert!(magic == MAGIC, "corrupted control block: bad magic {magic:#x}");
+
+            let live = self.counter.get();
+            assert!(valid_owners(live), "corrupted control block: {live} owners");
+        }
+    }
+
+    /// Counts an owner turned into a raw pointer or parts, with the
+    /// `checked_raw` feature; does nothing otherwise.
+    #[inline]
+    fn raw_given(&self) {
+        #[cfg(feature = "checked_raw")]
+        self.raw.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Panics unless an owner turned into a raw pointer or parts is left
+    /// to be reconstructed, with the `checked_raw` feature; does nothing
+    /// otherwise.
+    #[inline]
+    #[track_caller]
+    fn raw_taken(&self) {
+        #[cfg(feature = "checked_raw")]
+        {
+            let taken = self.raw.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+            assert!(taken.is_ok(), "reconstructed more owners than were turned into raw pointers");
+        }
+    }
+
+    /// Takes the callback registered with [`StaticArc::on_last_drop`].
+    fn take_on_last_drop(&self) -> Option<OnLastDrop> {
+        let f = self.on_last_drop.swap(core::ptr::null_mut(), Ordering::Acquire);
+
+        // SAFETY: non-null pointers were leaked from a `Box`
+        // in `StaticArc::on_last_drop`, and we swapped it out
+        (!f.is_null()).then(|| *unsafe { Box::from_raw(f) })
+    }
+
+    /// Drops the header of the allocation pointed to by `this`,
+    /// and frees it through the allocator stored in it.
+    ///
+    /// # Safety
+    ///
+    /// The value must have already been dropped or moved out,
+    /// and no references to the allocation may remain.
+    unsafe fn deallocate(this: NonNull<Self>) {
+        // SAFETY: the allocation is still valid, and nobody
+        // else will access its header
+        let (layout, alloc) = unsafe {
+            drop(this.as_ref().take_on_last_drop());
+            #[cfg(feature = "async")]
+            this.as_ref().waiters.clear();
+            #[cfg(feature = "park")]
+            this.as_ref().parker.clear();
+            #[cfg(feature = "debug_checks")]
+            this.as_ref().magic.store(0, Ordering::Relaxed);
+            #[cfg(feature = "metrics")]
+            if this.as_ref().counted {
+                metrics::freed();
+            }
+
+            let layout = Layout::for_value(this.as_ref());
+            let alloc = core::ptr::read(core::ptr::addr_of!((*this.as_ptr()).alloc));
+            (layout, alloc)
+        };
+
+        // SAFETY: `this` was allocated by `alloc`, with the same layout
+        unsafe {
+            alloc.deallocate(this.cast(), layout);
+        }
+    }
+
+    /// Releases a weak reference to the allocation pointed to by `this`,
+    /// freeing it if it was the last one.
+    ///
+    /// # Safety
+    ///
+    /// The caller must hold a weak reference, which it gives up; if it
+    /// was the last one, the value must have been dropped or moved out.
+    unsafe fn release_weak(this: NonNull<Self>) {
+        // SAFETY: the allocation is kept alive by our weak reference
+        if unsafe { this.as_ref() }.weak.fetch_sub(1, Ordering::Release) == 1 {
+            // synchronize with the release decrements of
+            // other weak references, before freeing
+            sync::fence(Ordering::Acquire);
+
+            // SAFETY: no more owners or weak references are alive,
+            // and the value has already been dropped or reclaimed
+            unsafe { StaticArcInner::deallocate(this) };
+        }
+    }
+
+    /// Keeps the allocation pointed to by `this` alive until the returned
+    /// guard is dropped, while the caller holds an owner which it's about
+    /// to give up, e.g.
+    /// to wake up the owners left behind.
+    ///
+    /// Unlike `StaticArc::downgrade`, this takes a single increment, which
+    /// never has to wait on the lock of `is_unique`: that lock is only
+    /// taken by the last owner, which the caller isn't, as long as at
+    /// least one other owner is alive.
+    #[cfg(any(feature = "async", feature = "park"))]
+    #[inline]
+    fn keep_alive(this: NonNull<Self>) -> ReleaseWeak<T, A, C> {
+        // relaxed, like cloning a `std::sync::Arc`: we hold a reference
+        // to the allocation already, which the new one is derived from
+        //
+        // SAFETY: the caller's owner keeps the allocation alive
+        let previous = unsafe { this.as_ref() }.weak.fetch_add(1, Ordering::Relaxed);
+        debug_assert_ne!(previous, usize::MAX, "kept alive a unique allocation");
+        if previous > MAX_REFCOUNT {
+            abort();
+        }
+
+        ReleaseWeak(this)
+    }
+
+    /// Returns a pointer to the value of the allocation pointed to by
+    /// `this`; it keeps the provenance of the whole allocation, which
+    /// `StaticArc::from_raw` relies on.
+    #[inline]
+    fn value_ptr(this: NonNull<Self>) -> *mut T {
+        // SAFETY: the allocation has already been initialized
+        let value = unsafe {
+            core::ptr::addr_of!((*this.as_ptr()).value)
+        };
+
+        // `ManuallyDrop<T>` is `repr(transparent)`
+        UnsafeCell::raw_get(value) as *mut T
+    }
+
+    /// Returns `true` if a single owner is left, and no weak references
+    /// exist, in which case that owner may access the value mutably.
+    fn is_unique(&self) -> bool {
+        // bail out early while other owners are alive, without taking the
+        // lock below, so that callers polling for uniqueness don't starve
+        // owners being dropped, which need to briefly downgrade
+        if self.counter.get() != 1 {
+            return false;
+        }
+
+        // lock the weak count, so no weak references can be upgraded
+        // while we check the strong count; this only succeeds if there
+        // are no outstanding weak references
+        //
+        // acquire, to synchronize with the release decrements
+        // of dropped weak references
+        let locked = self.weak.compare_exchange(
+            1,
+            usize::MAX,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        );
+
+        if locked.is_ok() {
+            // `get` synchronizes with the release decrements of dropped
+            // owners, so their accesses to the value happen before the
+            // mutable access the caller is about to hand out
+            let unique = self.counter.get() == 1;
+
+            // release, so that `downgrade` synchronizes with us
+            self.weak.store(1, Ordering::Release);
+            unique
+        } else {
+            false
+        }
+    }
+
+    /// Drops the value of the allocation pointed to by `this`, running
+    /// the callback registered with [`StaticArc::on_last_drop`] first,
+    /// and releases the weak reference held by the owners.
+    ///
+    /// # Safety
+    ///
+    /// The last owner must have been dropped, bringing the counter to 0.
+    unsafe fn drop_value(this: NonNull<Self>) {
+        trace!(
+            ty = core::any::type_name::<T>(),
+            addr = ?this.cast::<()>(),
+            "last owner dropped",
+        );
+
+        // release the weak reference held by the strong owners once we're
+        // done, which frees the allocation if no weak references remain;
+        // like `std::sync::Arc`, this happens even if dropping the value
+        // panics, and panicking again while unwinding aborts
+        let _weak = ReleaseWeak(this);
+
+        // the value is dropped even if the callback panics
+        //
+        // SAFETY: guaranteed by the caller
+        let _value = DropInPlace(Self::value_ptr(this));
+
+        // SAFETY: the allocation is kept alive by the weak reference
+        if let Some(f) = unsafe { this.as_ref() }.take_on_last_drop() {
+            f();
+        }
+    }
 }
 
-struct StaticArcInner<T> {
-    counter: AtomicUsize,
-    value: ManuallyDrop<T>,
+impl<T, A: Allocator, C: Counter> StaticArcInner<T, A, C> {
+    /// Moves the value out of the allocation pointed to by `this`, and
+    /// releases the weak reference held by the owners.
+    ///
+    /// # Safety
+    ///
+    /// The last owner must have brought the counter to 0, and
+    /// given up on dropping the value.
+    unsafe fn reclaim(this: NonNull<Self>) -> T {
+        // release the weak reference held by the strong owners,
+        // which frees the allocation if no weak references remain
+        let _weak = ReleaseWeak(this);
+
+        // the value isn't being dropped
+        //
+        // SAFETY: the allocation is kept alive by the weak reference
+        drop(unsafe { this.as_ref() }.take_on_last_drop());
+
+        // SAFETY: guaranteed by the caller
+        unsafe { core::ptr::read(Self::value_ptr(this)) }
+    }
 }
 
-unsafe impl<T> Send for StaticArc<T> {}
+/// `StaticArc<T>` may be sent to another thread when `T: Send + Sync`.
+///
+/// Owners of the same allocation may live on different threads and
+/// access the value concurrently through `Deref`, hence `T: Sync`.
+/// The last owner to be dropped drops the value on whichever thread it
+/// happens to live, hence `T: Send`. These are the same bounds as
+/// `std::sync::Arc`.
+///
+/// Values with non-atomic reference counts can't be moved across threads:
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use static_arc::StaticArc;
+///
+/// fn assert_send<S: Send>() {}
+///
+/// assert_send::<StaticArc<Rc<i32>>>();
+/// ```
+///
+/// Neither can values that are `Send` but not `Sync`, since a sibling
+/// owner might still be reading the value on the original thread:
+///
+/// ```compile_fail
+/// use std::cell::Cell;
+/// use static_arc::StaticArc;
+///
+/// fn assert_send<S: Send>() {}
+///
+/// assert_send::<StaticArc<Cell<i32>>>();
+/// ```
+unsafe impl<T: ?Sized + Send + Sync, A: Allocator + Send> Send for StaticArc<T, A> {}
+
+/// `StaticArc<T>` may be shared between threads when `T: Send + Sync`.
+///
+/// The allocator must be `Send`, since it is moved into whichever thread
+/// frees the allocation, in both cases.
+///
+/// Through a shared `&StaticArc<T>` we only ever hand out `&T` (via
+/// `Deref`) and touch the counter atomically, so this is as sound as
+/// sharing a `&T`. Mutable access requires `&mut StaticArc<T>`, which
+/// can't be obtained from shared references.
+///
+/// A value that isn't `Sync` can't be shared this way:
+///
+/// ```compile_fail
+/// use std::cell::Cell;
+/// use static_arc::StaticArc;
+///
+/// fn assert_sync<S: Sync>() {}
+///
+/// assert_sync::<StaticArc<Cell<i32>>>();
+/// ```
+unsafe impl<T: ?Sized + Send + Sync, A: Allocator + Send> Sync for StaticArc<T, A> {}
+
+// SAFETY: a shared control block hands out owners, which
+// require the same bounds; e.g. to be placed in a `static`
+unsafe impl<T: ?Sized + Send + Sync, A: Allocator + Send + Sync, C: Sync> Sync for StaticArcInner<T, A, C> {}
 
 impl<T> StaticArc<T> {
     #[inline]
@@ -22,138 +637,3640 @@ impl<T> StaticArc<T> {
         Self::new_recover(value).ok()
     }
 
+    /// Like [`StaticArc::new`], but rejects `N == 0` at compile time,
+    /// so it can't fail:
+    ///
+    /// ```compile_fail
+    /// use static_arc::StaticArc;
+    ///
+    /// let [] = StaticArc::new_const::<0>(1);
+    /// ```
+    #[inline]
+    pub fn new_const<const N: usize>(value: T) -> [Self; N] {
+        assert_owners::<N>();
+        Self::new_unchecked(value)
+    }
+
     pub fn new_recover<const N: usize>(value: T) -> Result<[Self; N], T> {
-        if N < 1 {
+        if !valid_owners(N) {
             return Err(value);
         }
 
-        let boxed = Box::new(StaticArcInner {
-            value: ManuallyDrop::new(value),
-            counter: AtomicUsize::new(N),
-        });
+        Ok(Self::new_unchecked(value))
+    }
 
-        // SAFETY: the boxed value has a valid heap address
-        let inner = unsafe { NonNull::new_unchecked(Box::into_raw(boxed)) };
+    /// Like [`StaticArc::new`], but only constructs the shared value
+    /// by calling `f` if it can actually be split into `N` owners.
+    pub fn new_with<const N: usize, F: FnOnce() -> T>(f: F) -> Option<[Self; N]> {
+        if !valid_owners(N) {
+            return None;
+        }
 
-        let mut array: MaybeUninit<[StaticArc<T>; N]> = MaybeUninit::uninit();
+        Some(Self::new_unchecked(f()))
+    }
 
-        // initialize array
-        for i in 0..N {
-            // SAFETY: the addr of `array` is not null,
-            // and we are pointing to an index in `array`
-            // when writing a value
-            unsafe {
-                array
-                    .as_mut_ptr()
-                    .cast::<StaticArc<T>>()
-                    .add(i)
-                    .write(StaticArc { inner })
-            }
-        }
+    /// Like [`StaticArc::new`], but places the shared value at an
+    /// `ALIGN`-byte boundary, which may be stricter than what `T`
+    /// requires.
+    ///
+    /// `ALIGN` must be a power of two up to 4096, or this doesn't compile:
+    ///
+    /// ```compile_fail
+    /// use static_arc::StaticArc;
+    ///
+    /// let _ = StaticArc::new_aligned::<1, 48>(0_u8);
+    /// ```
+    #[inline]
+    pub fn new_aligned<const N: usize, const ALIGN: usize>(
+        value: T,
+    ) -> Option<[StaticArc<Aligned<T, ALIGN>>; N]>
+    where
+        Align<ALIGN>: Alignment,
+    {
+        StaticArc::new(Aligned::new(value))
+    }
 
-        // SAFETY: we initialized `array`
-        Ok(unsafe { array.assume_init() })
+    /// Makes `groups` independent allocations, each holding a clone
+    /// of `value` shared between `N` owners.
+    ///
+    /// Like [`StaticArc::new_const`], this doesn't compile with `N == 0`:
+    ///
+    /// ```compile_fail
+    /// use static_arc::StaticArc;
+    ///
+    /// let _ = StaticArc::new_groups::<0>(1, 2);
+    /// ```
+    pub fn new_groups<const N: usize>(value: T, groups: usize) -> Vec<[Self; N]>
+    where
+        T: Clone,
+    {
+        assert_owners::<N>();
+
+        let mut all = Vec::with_capacity(groups);
+        if groups > 0 {
+            all.extend((1..groups).map(|_| Self::new_unchecked(value.clone())));
+            all.push(Self::new_unchecked(value));
+        }
+        all
     }
 
+    /// Shares a fresh default value between `N` owners.
     #[inline]
-    fn arc(&self) -> &mut StaticArcInner<T> {
-        // SAFETY: this `StaticArc` has already been initialized
-        unsafe { &mut *self.inner.as_ptr() }
+    pub fn new_default<const N: usize>() -> Option<[Self; N]>
+    where
+        T: Default,
+    {
+        Self::new_with(T::default)
+    }
+
+    /// Like [`StaticArc::new`], but returns pinned owners.
+    ///
+    /// The shared value lives at the same address until it is dropped,
+    /// so pinning it is sound. A pinned owner can't be turned back into an
+    /// unpinned one (unless `T: Unpin`), which means the value can neither
+    /// be mutated through [`StaticArc::get_mut`], nor moved out of
+    /// the allocation through [`StaticArc::try_into_inner`]:
+    ///
+    /// ```compile_fail
+    /// use std::marker::PhantomPinned;
+    /// use static_arc::StaticArc;
+    ///
+    /// let [p] = StaticArc::pin(PhantomPinned).unwrap();
+    /// let value = std::pin::Pin::into_inner(p).try_into_inner();
+    /// ```
+    pub fn pin<const N: usize>(value: T) -> Option<[Pin<Self>; N]> {
+        let owners = Self::new(value)?;
+
+        // SAFETY: the value is never moved while owners exist,
+        // and pinned owners can't be unpinned
+        Some(owners.map(|owner| unsafe { Pin::new_unchecked(owner) }))
     }
 
+    /// Splits an uninitialized allocation into `N` owners, to be
+    /// initialized later on.
+    ///
+    /// Every owner shares the same slot, so only one of them may write
+    /// the value (e.g. through [`StaticArc::as_ptr`]), and the others
+    /// must synchronize with the writer before reading it, or calling
+    /// [`StaticArc::assume_init`].
+    ///
+    /// Like [`StaticArc::new_const`], `N == 0` is rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// use static_arc::StaticArc;
+    ///
+    /// let [] = StaticArc::<i32>::new_uninit::<0>();
+    /// ```
     #[inline]
-    pub fn live(&self) -> NonZeroUsize {
-        let value = self.arc().counter.load(Ordering::SeqCst);
+    pub fn new_uninit<const N: usize>() -> [StaticArc<MaybeUninit<T>>; N] {
+        StaticArc::new_const(MaybeUninit::uninit())
+    }
 
-        // SAFETY: if we own a reference to `StaticArc`, the value
-        // of the counter will always be greater than 0
+    /// Like [`StaticArc::new_uninit`], but with the value's memory filled
+    /// with zeroes, straight from the allocator.
+    ///
+    /// The value is never built on the stack, so this suits large buffers,
+    /// which are valid when zeroed, paired with [`StaticArc::assume_init`].
+    pub fn new_zeroed<const N: usize>() -> Option<[StaticArc<MaybeUninit<T>>; N]> {
+        if !valid_owners(N) {
+            return None;
+        }
+
+        let layout = Layout::new::<StaticArcInner<MaybeUninit<T>, Global>>();
+        let inner = match Global.allocate_zeroed(layout) {
+            Ok(ptr) => ptr.cast::<StaticArcInner<MaybeUninit<T>, Global>>(),
+            Err(_) => handle_alloc_error(layout),
+        };
+
+        // SAFETY: `inner` points at a fresh allocation that fits a
+        // `StaticArcInner`, and a zeroed `MaybeUninit<T>` is a valid value
         unsafe {
-            NonZeroUsize::new_unchecked(value)
+            StaticArcInner::init(inner.as_ptr(), N, Global);
+            Some(StaticArc::from_inner(inner))
         }
     }
 
+    /// Like [`StaticArc::new_const`], but returns the value along with
+    /// the error if the allocation fails, instead of aborting.
+    ///
+    /// ```compile_fail
+    /// use static_arc::StaticArc;
+    ///
+    /// let _ = StaticArc::try_new::<0>(1);
+    /// ```
     #[inline]
-    pub fn try_as_ref_mut(&self) -> Option<&mut T> {
-        if self.live().get() == 1 {
-            Some(&mut self.arc().value)
-        } else {
-            None
-        }
+    pub fn try_new<const N: usize>(value: T) -> Result<[Self; N], (T, AllocError)> {
+        Self::try_new_in(value, Global)
     }
 
+    /// Shares two values together, in a single allocation
+    /// split into `N` owners.
     #[inline]
-    pub fn try_into_inner(self) -> Option<T> {
-        self.try_into_inner_recover().ok()
+    pub fn zip<U, const N: usize>(a: T, b: U) -> Option<[StaticArc<(T, U)>; N]> {
+        StaticArc::new((a, b))
     }
 
-    pub fn try_into_inner_recover(self) -> Result<T, Self> {
-        match self.try_as_ref_mut() {
-            Some(value) => {
-                // SAFETY: a single instance remains, so
-                // we can reclaim the allocated value
-                let value = unsafe {
-                    std::ptr::read(value as *const _)
-                };
+    /// Moves the values of two unique owners into a single allocation
+    /// split into `N` owners, like [`StaticArc::zip`].
+    ///
+    /// Fails, returning both owners, if either has other owners or weak
+    /// references, or `N` is 0.
+    #[allow(clippy::type_complexity)]
+    pub fn zip_existing<U, const N: usize>(
+        a: Self,
+        b: StaticArc<U>,
+    ) -> Result<[StaticArc<(T, U)>; N], (Self, StaticArc<U>)> {
+        // without weak references, no new owners may show up,
+        // so reclaiming both values can't fail past this point
+        if !valid_owners(N) || !Self::is_unique(&a) || !StaticArc::is_unique(&b) {
+            return Err((a, b));
+        }
+
+        let (Ok(a), Ok(b)) = (a.try_into_inner_recover(), b.try_into_inner_recover()) else {
+            unreachable!("unique owners failed to reclaim their values");
+        };
+        Ok(StaticArc::new_unchecked((a, b)))
+    }
+
+    /// Reclaims the value of a unique owner, and shares the result of
+    /// `f` on it in a fresh allocation split into `N` owners.
+    ///
+    /// Fails, returning the owner, if other owners are alive or `N` is 0.
+    /// This is an [associated function](StaticArc#associated-functions).
+    pub fn map_value<U, const N: usize, F>(this: Self, f: F) -> Result<[StaticArc<U>; N], Self>
+    where
+        F: FnOnce(T) -> U,
+    {
+        if !valid_owners(N) {
+            return Err(this);
+        }
+        let value = this.try_into_inner_recover()?;
+        Ok(StaticArc::new_unchecked(f(value)))
+    }
 
-                // SAFETY: manually drop `Box`, since we want to
-                // keep the inner value
-                let _ = unsafe { Box::from_raw(self.inner.as_ptr()) };
-                mem::forget(self);
+    /// Like [`StaticArc::map_value`], but with a fallible `f`, which
+    /// leaves the owner as it was if it fails.
+    ///
+    /// Since `f` consumes the value it converts, it's given a clone of
+    /// it, and the original value is only dropped once `f` succeeds, so
+    /// there's no need to restore it. On failure, the owner is returned
+    /// along with the error of `f`, or `None` if `f` didn't run at all,
+    /// because other owners or weak references exist, or `N` is 0.
+    #[allow(clippy::type_complexity)]
+    pub fn try_map_recover<U, E, const N: usize, F>(
+        this: Self,
+        f: F,
+    ) -> Result<[StaticArc<U>; N], (Self, Option<E>)>
+    where
+        T: Clone,
+        F: FnOnce(T) -> Result<U, E>,
+    {
+        // without weak references, no new owners may show up,
+        // so reclaiming the value can't fail past this point
+        if !valid_owners(N) || !Self::is_unique(&this) {
+            return Err((this, None));
+        }
 
-                Ok(value)
+        match f(T::clone(&this)) {
+            Ok(value) => {
+                let Ok(old) = this.try_into_inner_recover() else {
+                    unreachable!("a unique owner failed to reclaim its value");
+                };
+                drop(old);
+                Ok(StaticArc::new_unchecked(value))
             },
-            None => Err(self),
+            Err(err) => Err((this, Some(err))),
         }
     }
+
+    /// Like [`StaticArc::new`], but with a number of owners only
+    /// known at runtime, which are returned in a `Vec`.
+    ///
+    /// Returns `None` when `n` is 0, or too large to be counted.
+    #[inline]
+    pub fn new_dyn(value: T, n: usize) -> Option<Vec<Self>> {
+        Self::new_dyn_in(value, n, Global)
+    }
+
+    #[inline]
+    fn new_unchecked<const N: usize>(value: T) -> [Self; N] {
+        Self::new_unchecked_in(value, Global)
+    }
 }
 
-impl<T> Deref for StaticArc<T> {
-    type Target = T;
+impl<T: ?Sized> StaticArc<T> {
+    /// Moves a boxed value into a fresh allocation shared by `N` owners.
+    ///
+    /// Unlike [`StaticArc::new`], this works with unsized values,
+    /// like slices or trait objects.
+    pub fn from_box<const N: usize>(value: Box<T>) -> Option<[Self; N]> {
+        if !valid_owners(N) {
+            return None;
+        }
 
-    fn deref(&self) -> &T {
-        &self.arc().value
+        let value_layout = Layout::for_value::<T>(&value);
+        let (layout, offset) = StaticArcInner::<T, Global>::layout_for(value_layout);
+
+        let memory = match Global.allocate(layout) {
+            Ok(ptr) => ptr.cast::<u8>(),
+            Err(_) => handle_alloc_error(layout),
+        };
+
+        let value = Box::into_raw(value);
+        let inner = set_ptr_addr(value as *mut StaticArcInner<T, Global>, memory.as_ptr());
+
+        // SAFETY: `inner` points at a fresh allocation that fits a
+        // `StaticArcInner<T>` with the same metadata as `value`
+        unsafe {
+            StaticArcInner::init(inner, N, Global);
+            core::ptr::copy_nonoverlapping(
+                value.cast::<u8>(),
+                memory.as_ptr().add(offset),
+                value_layout.size(),
+            );
+
+            // free the box, without dropping the value we just moved
+            drop(Box::from_raw(value as *mut ManuallyDrop<T>));
+        }
+
+        // SAFETY: `inner` is non-null and fully initialized
+        Some(unsafe { Self::from_inner(NonNull::new_unchecked(inner)) })
+    }
+
+    /// Reconstructs an owner from a pointer returned by
+    /// [`StaticArc::into_raw`].
+    ///
+    /// The address of the allocation is recovered by subtracting the
+    /// offset of the value within it, which is only known for the
+    /// concrete type `T` of the original owner (and the alignment of
+    /// the value, if it is unsized).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by `StaticArc::<T>::into_raw`,
+    /// with this exact `T`, and each such pointer may be passed to
+    /// `from_raw` only once.
+    ///
+    /// With the `checked_raw` feature, reconstructing more owners than
+    /// were turned into raw pointers or parts panics, rather than
+    /// corrupting the count of owners; the allocation must still be
+    /// alive for the check to run, though.
+    #[track_caller]
+    pub unsafe fn from_raw(ptr: *const T) -> Self {
+        // SAFETY: upheld by the caller; a forged owner
+        // mustn't be dropped if the check panics
+        let owner = ManuallyDrop::new(unsafe { Self::from_raw_unchecked(ptr) });
+        owner.arc().raw_taken();
+        ManuallyDrop::into_inner(owner)
+    }
+
+    /// Like [`StaticArc::from_raw`], but without the `checked_raw`
+    /// bookkeeping, for owners accounted for otherwise.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point at the value of a live `StaticArc<T>`.
+    pub(crate) unsafe fn from_raw_unchecked(ptr: *const T) -> Self {
+        // SAFETY: the caller guarantees `ptr` points at a live value
+        let value_layout = Layout::for_value(unsafe { &*ptr });
+        let (_, offset) = StaticArcInner::<T, Global>::layout_for(value_layout);
+
+        // SAFETY: the caller guarantees `ptr` points at the value field
+        // of a live allocation, which starts `offset` bytes earlier; the
+        // allocation is aligned for `StaticArcInner<T>`, since that is how
+        // we allocated it
+        let inner = unsafe {
+            ptr.byte_sub(offset) as *mut StaticArcInner<T, Global>
+        };
+
+        StaticArc {
+            // SAFETY: `inner` was derived from a non-null `StaticArc`
+            inner: unsafe { NonNull::new_unchecked(inner) },
+        }
     }
 }
 
-impl<T> Drop for StaticArc<T> {
-    fn drop(&mut self) {
-        if self.arc().counter.fetch_sub(1, Ordering::SeqCst) == 1 {
-            // SAFETY: counter value reached 0, therefore
-            // no more `StaticArc` instances are alive
-            unsafe {
-                // drop value
-                ManuallyDrop::drop(&mut self.inner.as_mut().value);
+impl<T, A: Allocator> StaticArc<T, A> {
+    /// Like [`StaticArc::new`], but places the shared value in memory
+    /// obtained from `alloc`, which is also used to free it.
+    #[inline]
+    pub fn new_in<const N: usize>(value: T, alloc: A) -> Option<[Self; N]> {
+        if !valid_owners(N) {
+            return None;
+        }
+
+        Some(Self::new_unchecked_in(value, alloc))
+    }
+
+    // NB: with `N == 0` no owners are returned,
+    // and the allocation is leaked
+    fn new_unchecked_in<const N: usize>(value: T, alloc: A) -> [Self; N] {
+        let inner = Self::allocate_inner(value, N, alloc);
+
+        // SAFETY: `inner` is fully initialized
+        unsafe { Self::from_inner(inner) }
+    }
+
+    /// Moves `value` into a fresh allocation with `count` owners.
+    fn allocate_inner(value: T, count: usize, alloc: A) -> NonNull<StaticArcInner<T, A>> {
+        match Self::try_allocate_inner(value, count, alloc) {
+            Ok(inner) => inner,
+            Err(_) => handle_alloc_error(Layout::new::<StaticArcInner<T, A>>()),
+        }
+    }
+
+    /// Like [`StaticArc::allocate_inner`], but hands the value
+    /// back if the allocation fails.
+    fn try_allocate_inner(
+        value: T,
+        count: usize,
+        alloc: A,
+    ) -> Result<NonNull<StaticArcInner<T, A>>, (T, AllocError)> {
+        let layout = Layout::new::<StaticArcInner<T, A>>();
+
+        let inner = match alloc.allocate(layout) {
+            Ok(ptr) => ptr.cast::<StaticArcInner<T, A>>(),
+            Err(err) => return Err((value, err)),
+        };
+
+        // SAFETY: `inner` points at a fresh allocation
+        // that fits a `StaticArcInner<T, A>`
+        unsafe {
+            StaticArcInner::init(inner.as_ptr(), count, alloc);
+            core::ptr::addr_of_mut!((*inner.as_ptr()).value)
+                .write(UnsafeCell::new(ManuallyDrop::new(value)));
+        }
+
+        Ok(inner)
+    }
+
+    /// Like [`StaticArc::new_in`], but returns the value along with
+    /// the error if the allocation fails, instead of aborting.
+    ///
+    /// Like [`StaticArc::new_const`], this doesn't compile with `N == 0`:
+    ///
+    /// ```compile_fail
+    /// use static_arc::{Global, StaticArc};
+    ///
+    /// let _ = StaticArc::try_new_in::<0>(1, Global);
+    /// ```
+    pub fn try_new_in<const N: usize>(value: T, alloc: A) -> Result<[Self; N], (T, AllocError)> {
+        assert_owners::<N>();
+        let inner = Self::try_allocate_inner(value, N, alloc)?;
+
+        // SAFETY: `inner` is fully initialized
+        Ok(unsafe { Self::from_inner(inner) })
+    }
+
+    /// Like [`StaticArc::new_in`], but with a number of owners
+    /// only known at runtime.
+    pub fn new_dyn_in(value: T, n: usize, alloc: A) -> Option<Vec<Self>> {
+        if !valid_owners(n) {
+            return None;
+        }
+
+        // allocate the owners first, so we don't leak the
+        // shared value if we run out of memory
+        let mut owners = Vec::with_capacity(n);
+        let inner = Self::allocate_inner(value, n, alloc);
+        owners.resize_with(n, || StaticArc { inner });
+
+        Some(owners)
+    }
+
+    /// Reclaims the shared value if this is the only owner left,
+    /// or drops the owner otherwise.
+    ///
+    /// The result must be used, since ignoring it would drop the value
+    /// unnoticed:
+    ///
+    /// ```compile_fail
+    /// #![deny(unused_must_use)]
+    /// use static_arc::StaticArc;
+    ///
+    /// let [p1] = StaticArc::new(5).unwrap();
+    /// p1.try_into_inner();
+    /// ```
+    #[must_use = "ignoring the result drops the value, if it was reclaimed"]
+    #[inline]
+    pub fn try_into_inner(self) -> Option<T> {
+        self.try_into_inner_recover().ok()
+    }
+
+    /// Returns a future that resolves into the shared value,
+    /// once every other owner has been dropped.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn into_inner_async(self) -> IntoInner<T, A> {
+        IntoInner::new(self)
+    }
+
+    /// Blocks until every other owner has been dropped, then
+    /// returns the shared value.
+    ///
+    /// Waiting spins with an exponential backoff, which
+    /// eventually yields to the scheduler between checks
+    /// when the `std` feature is enabled.
+    pub fn into_inner_spin(self) -> T {
+        let mut backoff = backoff::Backoff::new();
+        self.into_inner_snoozing(|| backoff.snooze())
+    }
 
-                // drop box allocation
-                let _ = Box::from_raw(self.inner.as_ptr());
+    /// Loops until every other owner has been dropped, calling `snooze`
+    /// in between checks, then returns the shared value.
+    fn into_inner_snoozing(self, mut snooze: impl FnMut()) -> T {
+        let mut this = self;
+
+        loop {
+            match this.try_into_inner_recover() {
+                Ok(value) => return value,
+                Err(owner) => this = owner,
             }
+            snooze();
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::sync::Mutex;
+    /// Like [`StaticArc::into_inner_spin`], but backs off with
+    /// `crossbeam_utils::Backoff`, for consistency with code already
+    /// tuned around it.
+    ///
+    /// Instead of a hand-written loop around
+    /// [`StaticArc::try_into_inner_recover`]:
+    ///
+    /// ```
+    /// use static_arc::StaticArc;
+    ///
+    /// let [main, worker] = StaticArc::new(vec![1, 2, 3]).unwrap();
+    /// std::thread::spawn(move || assert_eq!(worker.len(), 3));
+    ///
+    /// assert_eq!(main.into_inner_backoff(), [1, 2, 3]);
+    /// ```
+    #[cfg(feature = "crossbeam")]
+    pub fn into_inner_backoff(self) -> T {
+        let backoff = crossbeam_utils::Backoff::new();
+        let mut this = self;
 
-    #[test]
-    fn test_ref_mut() {
-        let [p1, p2, p3, p4] = StaticArc::new(Mutex::new(1234)).unwrap();
-        std::thread::spawn(move || {
-            drop((p2, p3));
-        });
-        assert_eq!(*p4.lock().unwrap(), 1234);
-        drop(p4);
         loop {
-            match p1.try_as_ref_mut() {
-                Some(p) => {
-                    *p.lock().unwrap() = 420;
-                    break;
-                },
-                _ => (),
+            match this.try_into_inner_recover() {
+                Ok(value) => return value,
+                Err(owner) => this = owner,
             }
+            backoff.snooze();
         }
-        let x = p1.try_into_inner().unwrap();
-        assert_eq!(*x.lock().unwrap(), 420);
+    }
+
+    /// Blocks until every other owner has been dropped, then
+    /// returns the shared value.
+    ///
+    /// Unlike [`StaticArc::into_inner_spin`], the current thread
+    /// is parked while waiting, and unparked by the second to
+    /// last owner as it gets dropped.
+    ///
+    /// The thread registers itself in a slot of the allocation, which
+    /// has room for a single waiter; were another owner waiting at the
+    /// same time, which could only succeed once this one is dropped,
+    /// this one would spin instead.
+    #[cfg(feature = "park")]
+    pub fn into_inner_wait(self) -> T {
+        match self.wait_unique(|registered| {
+            if registered {
+                sync::park();
+            }
+            true
+        }) {
+            Ok(value) => value,
+            Err(_) => unreachable!("waiting without a timeout gave up"),
+        }
+    }
+
+    /// Like [`StaticArc::into_inner_wait`], but gives up once `timeout`
+    /// elapses, returning the owner, e.g. in case another owner is held
+    /// by a thread that never drops it.
+    #[cfg(feature = "park")]
+    pub fn into_inner_timeout(self, timeout: Duration) -> Result<T, Self> {
+        let deadline = std::time::Instant::now().checked_add(timeout);
+
+        self.wait_unique(|registered| match deadline {
+            Some(deadline) => {
+                let now = std::time::Instant::now();
+                if now >= deadline {
+                    return false;
+                }
+                if registered {
+                    std::thread::park_timeout(deadline - now);
+                }
+                true
+            },
+            None => {
+                if registered {
+                    sync::park();
+                }
+                true
+            },
+        })
+    }
+
+    /// Waits until every other owner has been dropped, calling `park`
+    /// to block until the second to last one unparks us, which returns
+    /// `false` to give up.
+    ///
+    /// `park` is told whether we are registered to be unparked; if
+    /// another waiter is, it is called between spins instead, and
+    /// must not block.
+    #[cfg(feature = "park")]
+    fn wait_unique(self, mut park: impl FnMut(bool) -> bool) -> Result<T, Self> {
+        let mut backoff = backoff::Backoff::new();
+        let mut waiter = Waiter::current();
+        let mut this = self;
+
+        loop {
+            // a registration left behind is dropped along with the
+            // header, since the allocation may already be gone
+            match this.try_into_inner_recover() {
+                Ok(value) => return Ok(value),
+                Err(owner) => this = owner,
+            }
+
+            // registers again once an owner took our registration
+            // to unpark us, which lets go of it
+            let parker = &this.arc().parker;
+            if !parker.register(&mut waiter) {
+                if !park(false) {
+                    return Err(this);
+                }
+                backoff.snooze();
+                continue;
+            }
+
+            // parking may return early, so always re-check the count
+            // after waking up; it is re-checked after registering too,
+            // in case the owner we are waiting for is already gone
+            if this.arc().counter.load(Ordering::Relaxed) != 1 && !park(true) {
+                this.arc().parker.unregister(&waiter);
+                return Err(this);
+            }
+        }
+    }
+
+    /// Drops all but one of `owners`, and waits until every other owner
+    /// is dropped as well, to return the shared value.
+    ///
+    /// The thread is parked while waiting, like with
+    /// `StaticArc::into_inner_wait`; without the `park` feature,
+    /// it spins instead, like with [`StaticArc::into_inner_spin`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the owners don't share the same allocation, which
+    /// would otherwise wait forever. Passing no owners doesn't compile:
+    ///
+    /// ```compile_fail
+    /// use static_arc::StaticArc;
+    ///
+    /// let owners: [StaticArc<i32>; 0] = [];
+    /// StaticArc::gather(owners);
+    /// ```
+    pub fn gather<const N: usize>(owners: [Self; N]) -> T {
+        assert_owners::<N>();
+
+        let mut owners = owners.into_iter();
+        let first = owners.next().unwrap();
+        for owner in owners {
+            assert!(StaticArc::ptr_eq(&first, &owner), "owners of different allocations");
+        }
+
+        #[cfg(feature = "park")]
+        return first.into_inner_wait();
+
+        #[cfg(not(feature = "park"))]
+        first.into_inner_spin()
+    }
+
+    /// Consumes this owner, returning a pointer to its allocation
+    /// and a token to reassemble it with [`StaticArc::from_parts`].
+    ///
+    /// Like with [`StaticArc::into_raw`], the owner isn't dropped, so it
+    /// still counts towards `live()`.
+    ///
+    /// The token is only the size of `T`. With debug assertions, it catches
+    /// parts reassembled as a type of a different size, but it's no proof
+    /// of the type: nothing tells apart types of the same size, nor the
+    /// allocator, so that's left to the caller of [`StaticArc::from_parts`].
+    #[inline]
+    pub fn into_parts(self) -> (NonNull<()>, usize) {
+        self.arc().raw_given();
+        let inner = self.inner.cast::<()>();
+        mem::forget(self);
+        (inner, mem::size_of::<T>())
+    }
+
+    /// Reassembles an owner taken apart by [`StaticArc::into_parts`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` and `token` must have been returned by `into_parts` for an
+    /// owner of type `StaticArc<T, A>`, with this exact `T` and `A`, which
+    /// the token doesn't check beyond the size of `T`, and
+    /// each pair of parts may be reassembled only once: reassembling
+    /// an owner more than once would create more owners than counted,
+    /// which panics instead with the `checked_raw` feature, like with
+    /// [`StaticArc::from_raw`].
+    #[inline]
+    #[track_caller]
+    pub unsafe fn from_parts(ptr: NonNull<()>, token: usize) -> Self {
+        debug_assert_eq!(token, mem::size_of::<T>(), "parts of a type of a different size");
+        // a forged owner mustn't be dropped if the check panics
+        let owner = ManuallyDrop::new(Self { inner: ptr.cast() });
+        owner.arc().raw_taken();
+        ManuallyDrop::into_inner(owner)
+    }
+
+    /// Replaces the shared value, returning the old one, if this is the
+    /// only owner left and no weak references exist. Otherwise, the new
+    /// value is handed back.
+    ///
+    /// This is an [associated function](StaticArc#associated-functions).
+    #[inline]
+    pub fn replace(this: &mut Self, value: T) -> Result<T, T> {
+        match Self::get_mut(this) {
+            Some(old) => Ok(mem::replace(old, value)),
+            None => Err(value),
+        }
+    }
+
+    /// Takes the shared value, leaving `T::default()` in its place, if
+    /// this is the only owner left and no weak references exist.
+    ///
+    /// Like [`StaticArc::replace`], this is an [associated function](StaticArc#associated-functions).
+    #[inline]
+    pub fn take(this: &mut Self) -> Option<T>
+    where
+        T: Default,
+    {
+        Self::get_mut(this).map(mem::take)
+    }
+
+    /// Returns a clone of the shared value, leaving the owner in place.
+    #[inline]
+    pub fn make_owned(this: &Self) -> T
+    where
+        T: Clone,
+    {
+        T::clone(this)
+    }
+
+    /// Returns the shared value, moving it out if this is the only
+    /// owner left, or cloning it otherwise, like a copy-on-write
+    /// `std::sync::Arc::unwrap_or_clone`.
+    #[inline]
+    pub fn make_owned_cheap(this: Self) -> T
+    where
+        T: Clone,
+    {
+        this.try_into_inner_recover()
+            .unwrap_or_else(|this| Self::make_owned(&this))
+    }
+
+    /// Moves the shared value into a fresh `std::sync::Arc`, if this
+    /// is the only owner left. Otherwise, the owner is returned.
+    ///
+    /// This is unavailable on targets without native atomics, where
+    /// `alloc` doesn't provide `Arc`.
+    #[cfg(target_has_atomic = "ptr")]
+    #[must_use = "ignoring the result drops either the reclaimed value, or this owner"]
+    #[inline]
+    pub fn into_std_arc(self) -> Result<Arc<T>, Self> {
+        self.try_into_inner_recover().map(Arc::new)
+    }
+
+    /// Moves the shared value into a `Box`, if this is the only owner
+    /// left. Otherwise, the owner is returned.
+    ///
+    /// The shared allocation can't be reused for the box, since weak
+    /// references may still point at it, so the value is moved into
+    /// a fresh allocation.
+    #[must_use = "ignoring the result drops either the reclaimed value, or this owner"]
+    #[inline]
+    pub fn into_box(self) -> Result<Box<T>, Self> {
+        self.try_into_inner_recover().map(Box::new)
+    }
+
+    /// Reclaims the shared value if this is the only owner left,
+    /// or returns the owner otherwise.
+    ///
+    /// The count is compared with 1 and brought to 0 in a single atomic
+    /// step, rather than merely loaded, so a racing upgrade of a weak
+    /// reference either sees the value gone, or makes this fail.
+    #[must_use = "ignoring the result drops either the reclaimed value, or this owner"]
+    pub fn try_into_inner_recover(self) -> Result<T, Self> {
+        self.try_into_inner_detailed().map_err(|(this, _)| this)
+    }
+
+    /// Reclaims the shared value, without checking whether other
+    /// owners are alive, e.g. once a barrier shows they're gone.
+    ///
+    /// # Safety
+    ///
+    /// Every other owner must have been dropped, and their drops must
+    /// happen before this call, e.g. through a `std::sync::Barrier`.
+    /// Weak references must not be upgraded concurrently.
+    ///
+    /// This is checked with debug assertions, as far as possible.
+    pub unsafe fn assume_unique(self) -> T {
+        debug_assert_eq!(self.live().get(), 1, "other owners are alive");
+
+        // weak references can't be upgraded anymore
+        self.arc().counter.store(0, Ordering::Relaxed);
+
+        // SAFETY: the caller guarantees a single instance remained
+        unsafe { self.reclaim() }
+    }
+
+    /// Moves the value out of the allocation, and releases the weak
+    /// reference held by the strong owners.
+    ///
+    /// # Safety
+    ///
+    /// This must be the last owner, and the counter must be 0.
+    unsafe fn reclaim(self) -> T {
+        let inner = self.inner;
+        mem::forget(self);
+
+        // SAFETY: guaranteed by the caller
+        unsafe { StaticArcInner::reclaim(inner) }
+    }
+
+    /// Like [`StaticArc::try_into_inner_recover`], but also reports how
+    /// many owners were alive when reclaiming the value failed, e.g.
+    /// for logging.
+    ///
+    /// The count is the one which made reclaiming fail, so it's never
+    /// 1; other owners may be dropped concurrently, so it may already
+    /// be stale once it's returned.
+    #[must_use = "ignoring the result drops either the reclaimed value, or this owner"]
+    pub fn try_into_inner_detailed(self) -> Result<T, (Self, usize)> {
+        // bringing the counter to 0 prevents weak
+        // references from being upgraded
+        if let Err(live) = self.arc().counter.try_reclaim() {
+            return Err((self, live));
+        }
+
+        // SAFETY: a single instance remained, and the
+        // counter was brought to 0
+        Ok(unsafe { self.reclaim() })
+    }
+
+    /// Like [`StaticArc::try_into_inner_detailed`], but with an error
+    /// type which can be propagated with `?`, or logged.
+    #[must_use = "ignoring the result drops either the reclaimed value, or this owner"]
+    #[inline]
+    pub fn try_into_inner_err(self) -> Result<T, ReclaimError<T, A>> {
+        self.try_into_inner_detailed()
+            .map_err(|(owner, live)| ReclaimError::new(owner, live))
+    }
+
+    /// Reclaims the shared value if this is the only owner left, like
+    /// `Option::expect`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with `msg` and the number of owners alive otherwise.
+    #[track_caller]
+    pub fn expect_unique(self, msg: &str) -> T {
+        match self.try_into_inner_detailed() {
+            Ok(value) => value,
+            Err((_, live)) => panic!("{msg}: {live} owners still live"),
+        }
+    }
+
+    /// Reclaims the shared value if this is the only owner left, like
+    /// `Option::unwrap`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the number of owners alive otherwise.
+    #[track_caller]
+    pub fn unwrap_unique(self) -> T {
+        self.expect_unique("called `StaticArc::unwrap_unique` on a shared owner")
+    }
+
+    /// An alias of [`StaticArc::try_into_inner_recover`], named after
+    /// `std::sync::Arc::try_unwrap`.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use static_arc::StaticArc;
+    ///
+    /// let a = Arc::new(5);
+    /// let b = Arc::clone(&a);
+    /// let a = Arc::try_unwrap(a).unwrap_err();
+    /// drop(b);
+    /// assert_eq!(Arc::try_unwrap(a), Ok(5));
+    ///
+    /// let [p1, p2] = StaticArc::new(5).unwrap();
+    /// let p1 = StaticArc::try_unwrap(p1).unwrap_err();
+    /// drop(p2);
+    /// assert_eq!(StaticArc::try_unwrap(p1).ok(), Some(5));
+    /// ```
+    #[must_use = "ignoring the result drops either the reclaimed value, or this owner"]
+    #[inline]
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        this.try_into_inner_recover()
+    }
+}
+
+impl<T, A: Allocator> StaticArc<MaybeUninit<T>, A> {
+    /// Converts an owner of an uninitialized value into an owner of `T`.
+    ///
+    /// Every owner should be converted: if the last one to be dropped is
+    /// still an owner of `MaybeUninit<T>`, the value is leaked.
+    ///
+    /// # Safety
+    ///
+    /// The value must have been initialized, and the write must happen
+    /// before this call, e.g. by a `Barrier` or a channel.
+    pub unsafe fn assume_init(this: Self) -> StaticArc<T, A> {
+        let inner = this.inner.cast::<StaticArcInner<T, A>>();
+        mem::forget(this);
+
+        // `MaybeUninit<T>` has the same layout as `T`,
+        // and with it the `repr(C)` inner type
+        StaticArc { inner }
+    }
+
+    /// Initializes the shared value with `f`, unless another owner did,
+    /// or is doing so; returns whether this call ran `f`.
+    ///
+    /// Every call returns once the value is initialized, and the write
+    /// happens before that, so any owner may then call
+    /// [`StaticArc::assume_init`]. Until then, other owners must not
+    /// access the value. If `f` panics, the next caller runs its own.
+    pub fn init_once<F: FnOnce() -> T>(this: &Self, f: F) -> bool {
+        let init = &this.arc().init;
+        let mut backoff = backoff::Backoff::new();
+
+        loop {
+            // acquire, to see the value written by the
+            // call that brought the state to done
+            match init.compare_exchange_weak(
+                INIT_NONE,
+                INIT_RUNNING,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(INIT_DONE) => return false,
+                Err(_) => backoff.snooze(),
+            }
+        }
+
+        /// Lets another caller try again if `f` panics.
+        struct Reset<'a>(&'a AtomicU8);
+
+        impl Drop for Reset<'_> {
+            fn drop(&mut self) {
+                self.0.store(INIT_NONE, Ordering::Release);
+            }
+        }
+
+        let reset = Reset(init);
+        let value = f();
+        mem::forget(reset);
+
+        // SAFETY: we are the only caller allowed to write the value,
+        // and other owners don't access it until we are done
+        unsafe { this.value_ptr().write(MaybeUninit::new(value)) };
+        init.store(INIT_DONE, Ordering::Release);
+
+        true
+    }
+}
+
+impl<T: ?Sized, A: Allocator> StaticArc<T, A> {
+    /// Splits an allocation into `N` owners.
+    ///
+    /// # Safety
+    ///
+    /// `inner` must point at a fully initialized allocation,
+    /// whose counter is set to `N`.
+    unsafe fn from_inner<const N: usize>(inner: NonNull<StaticArcInner<T, A>>) -> [Self; N] {
+        let mut array: MaybeUninit<[StaticArc<T, A>; N]> = MaybeUninit::uninit();
+
+        // initialize array
+        for i in 0..N {
+            // SAFETY: the addr of `array` is not null,
+            // and we are pointing to an index in `array`
+            // when writing a value
+            unsafe {
+                array
+                    .as_mut_ptr()
+                    .cast::<StaticArc<T, A>>()
+                    .add(i)
+                    .write(StaticArc { inner })
+            }
+        }
+
+        // SAFETY: we initialized `array`
+        unsafe { array.assume_init() }
+    }
+
+    #[inline]
+    fn arc(&self) -> &StaticArcInner<T, A> {
+        // SAFETY: this `StaticArc` has already been initialized
+        unsafe { self.inner.as_ref() }
+    }
+
+    #[inline]
+    fn value_ptr(&self) -> *mut T {
+        StaticArcInner::value_ptr(self.inner)
+    }
+
+    /// Removes this owner from the counter, returning the previous count,
+    /// like [`Counter::decrement`].
+    #[cfg(not(any(feature = "async", feature = "park")))]
+    #[inline]
+    fn decrement(this: &Self) -> usize {
+        this.arc().counter.decrement()
+    }
+
+    /// Removes this owner from the counter, returning the previous count,
+    /// like [`Counter::decrement`], and wakes up the owner left behind if
+    /// only one remains.
+    #[cfg(any(feature = "async", feature = "park"))]
+    fn decrement(this: &Self) -> usize {
+        let counter = &this.arc().counter;
+        let mut current = counter.load(Ordering::Relaxed);
+
+        loop {
+            // more owners were dropped than counted, e.g. by reassembling
+            // one too many with `from_raw`
+            debug_assert!(current >= 1, "owner dropped with a count of 0");
+
+            // if we are about to leave a single owner behind, we must wake
+            // it up after decrementing; at that point the allocation could
+            // be freed by that owner, unless we keep it alive with a weak reference
+            let alive = (current == 2).then(|| StaticArcInner::keep_alive(this.inner));
+
+            // release, so that any accesses to the value through this owner
+            // happen before the value is dropped or reclaimed by another one
+            match counter.compare_exchange_weak(
+                current,
+                current - 1,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    if alive.is_some() {
+                        #[cfg(feature = "async")]
+                        this.arc().waiters.wake_all();
+                        #[cfg(feature = "park")]
+                        this.arc().parker.unpark();
+                    }
+                    break;
+                },
+                Err(old) => current = old,
+            }
+        }
+
+        // as in `Counter::decrement`, synchronize with the release
+        // decrements of the other owners before dropping the value
+        if current == 1 {
+            sync::fence(Ordering::Acquire);
+        }
+
+        current
+    }
+
+    /// Returns a pointer to the allocation, i.e. its control block,
+    /// which is the same for every owner of the allocation, e.g. to key
+    /// registries by allocation.
+    ///
+    /// The pointer is only meant to identify the allocation; the layout
+    /// of the control block is private to this crate.
+    #[inline]
+    pub fn inner_ptr(&self) -> NonNull<()> {
+        self.inner.cast()
+    }
+
+    /// Returns the address of the allocation, as returned by
+    /// [`StaticArc::inner_ptr`], e.g. for logging, or as a key to
+    /// identify the allocation by.
+    #[inline]
+    pub fn addr(&self) -> usize {
+        self.inner_ptr().as_ptr() as usize
+    }
+
+    /// Returns a pointer to the shared value, which is the same
+    /// for every owner of the allocation.
+    ///
+    /// The pointer is valid for as long as this owner is alive.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.value_ptr()
+    }
+
+    /// Consumes this owner, returning a pointer to the shared value.
+    ///
+    /// The owner isn't dropped, so it still counts towards `live()`;
+    /// the pointer must be turned back into an owner with
+    /// [`StaticArc::from_raw`] to release it.
+    #[inline]
+    pub fn into_raw(self) -> *const T {
+        self.arc().raw_given();
+        let ptr = self.value_ptr();
+        mem::forget(self);
+        ptr
+    }
+
+    /// Consumes this owner without releasing it, returning a reference
+    /// to the shared value which lives for as long as the allocator does,
+    /// like `Box::leak`.
+    ///
+    /// The owner keeps counting towards `live()`, so the allocation is
+    /// never freed, whether or not other owners are alive. This is an
+    /// [associated function](StaticArc#associated-functions).
+    ///
+    /// Unlike [`StaticArc::into_raw`], the owner can't be reconstructed,
+    /// so it isn't counted as a raw pointer with the `checked_raw` feature.
+    #[inline]
+    pub fn leak<'a>(this: Self) -> &'a T
+    where
+        A: 'a,
+    {
+        let ptr = this.value_ptr();
+        mem::forget(this);
+
+        // SAFETY: the owner is never dropped, so neither is the value
+        unsafe { &*ptr }
+    }
+
+    /// Splits a unique owner into `M` fresh owners of the same
+    /// allocation, without going through the allocator.
+    ///
+    /// Fails, returning the owner, if other owners are alive. Like
+    /// [`StaticArc::new_const`], this doesn't compile with `M == 0`:
+    ///
+    /// ```compile_fail
+    /// use static_arc::StaticArc;
+    ///
+    /// let [p1] = StaticArc::new_const::<1>(5);
+    /// let _ = p1.resplit::<0>();
+    /// ```
+    pub fn resplit<const M: usize>(self) -> Result<[Self; M], Self> {
+        assert_owners::<M>();
+
+        // release, so that writes made through `get_mut` before resplitting
+        // happen before accesses through weak references upgraded with
+        // acquire afterwards; the new owners themselves can only reach
+        // other threads through something that synchronizes, like a
+        // channel; weak references being upgraded count on top of `M`
+        let resplit = self.arc().counter.compare_exchange(
+            1,
+            M,
+            Ordering::Release,
+            Ordering::Relaxed,
+        );
+
+        if resplit.is_err() {
+            return Err(self);
+        }
+        self.arc().original.store(M, Ordering::Relaxed);
+
+        let inner = self.inner;
+        mem::forget(self);
+
+        // SAFETY: the allocation is initialized, and the
+        // counter accounts for the owners returned
+        Ok(unsafe { Self::from_inner(inner) })
+    }
+
+    /// Splits a unique owner into two groups of `L` and `R` fresh owners
+    /// of the same allocation, e.g. to hand the shared value to two
+    /// subsystems.
+    ///
+    /// Fails, returning the owner, if other owners are alive. This
+    /// doesn't compile with `L + R == 0`:
+    ///
+    /// ```compile_fail
+    /// use static_arc::StaticArc;
+    ///
+    /// let [p1] = StaticArc::new_const::<1>(5);
+    /// let _ = p1.split_at::<0, 0>();
+    /// ```
+    pub fn split_at<const L: usize, const R: usize>(self) -> Result<([Self; L], [Self; R]), Self> {
+        assert_split::<L, R>();
+        let total = L + R;
+
+        // same as in `resplit`
+        let split = self.arc().counter.compare_exchange(
+            1,
+            total,
+            Ordering::Release,
+            Ordering::Relaxed,
+        );
+
+        if split.is_err() {
+            return Err(self);
+        }
+        self.arc().original.store(total, Ordering::Relaxed);
+
+        let inner = self.inner;
+        mem::forget(self);
+
+        // SAFETY: the allocation is initialized, and the
+        // counter accounts for the owners of both groups
+        Ok(unsafe { (Self::from_inner(inner), Self::from_inner(inner)) })
+    }
+
+    /// Wraps this owner so that it compares and hashes by the allocation
+    /// it belongs to, rather than by the shared value.
+    #[inline]
+    pub fn by_address(self) -> ByAddress<T, A> {
+        ByAddress(self)
+    }
+
+    /// Projects the owner into a part of the shared value, keeping the
+    /// whole allocation alive, like `owning_ref` does. This neither
+    /// adds nor removes owners.
+    ///
+    /// This is an [associated function](StaticArc#associated-functions).
+    #[inline]
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> StaticArcProjected<T, U, A>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        StaticArcProjected::new(this, f)
+    }
+
+    /// Returns `true` if both owners share the same allocation,
+    /// in the spirit of `std::sync::Arc::ptr_eq`.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        core::ptr::addr_eq(this.inner.as_ptr(), other.inner.as_ptr())
+    }
+
+    /// Compares two arrays of owners by their shared values, pairwise.
+    ///
+    /// This is the same as comparing the arrays with `==`, spelled out
+    /// for assertions over owners returned by [`StaticArc::new`], whose
+    /// comparison then boils down to that of their two shared values.
+    #[inline]
+    pub fn array_eq<const N: usize>(a: &[Self; N], b: &[Self; N]) -> bool
+    where
+        T: PartialEq,
+    {
+        a == b
+    }
+
+    /// Returns `Poll::Ready` once this is the only owner left, or
+    /// registers `cx`'s waker, to be woken by the second to last owner
+    /// as it gets dropped.
+    ///
+    /// This is the building block of [`StaticArc::into_inner_async`], for
+    /// custom futures, which may then reclaim the value with
+    /// [`StaticArc::try_into_inner`]. Every task waiting on the allocation
+    /// is woken, not only the last one to register.
+    ///
+    /// Polling again replaces the waker of the same task, and the waker is
+    /// taken out once this is ready. A caller which gives up before that
+    /// leaves its waker behind until the next wake up, as it can't be told
+    /// apart from others, so a future that may be dropped early should use
+    /// [`StaticArc::into_inner_async`] instead, which cleans up after itself.
+    #[cfg(feature = "async")]
+    pub fn poll_unique(this: &Self, cx: &mut Context<'_>) -> Poll<()> {
+        // register before checking the count, so we can't
+        // miss the wake up from the second to last owner
+        let key = this.arc().waiters.register(None, cx.waker());
+
+        if this.live().get() == 1 {
+            this.arc().waiters.deregister(key);
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    #[inline]
+    pub fn live(&self) -> NonZeroUsize {
+        self.arc().check();
+        let value = self.arc().counter.get();
+
+        // SAFETY: if we own a reference to `StaticArc`, the value
+        // of the counter will always be greater than 0
+        unsafe {
+            NonZeroUsize::new_unchecked(value)
+        }
+    }
+
+    /// Like [`StaticArc::live`], but with a relaxed load, for logging
+    /// or metrics.
+    ///
+    /// The count may be stale, so don't use it for uniqueness decisions:
+    /// observing a count of 1 doesn't mean the accesses of dropped owners
+    /// are visible to this thread.
+    ///
+    /// ```
+    /// use static_arc::StaticArc;
+    ///
+    /// let [mut p1, p2] = StaticArc::new(vec![1]).unwrap();
+    /// drop(p2);
+    /// println!("{} owners left", p1.live_relaxed());
+    ///
+    /// // don't: `get_mut` checks for uniqueness with the proper ordering
+    /// // if p1.live_relaxed().get() == 1 { ... }
+    /// if let Some(v) = StaticArc::get_mut(&mut p1) {
+    ///     v.push(2);
+    /// }
+    /// ```
+    #[inline]
+    pub fn live_relaxed(&self) -> NonZeroUsize {
+        let value = self.arc().counter.load(Ordering::Relaxed);
+
+        // SAFETY: as in `live`, our own owner is counted in any
+        // value we may observe
+        unsafe {
+            NonZeroUsize::new_unchecked(value)
+        }
+    }
+
+    /// Formats the owner like its `Debug` implementation, but leaving
+    /// out the shared value, so that `T` doesn't need to be `Debug`.
+    /// This is an [associated function](StaticArc#associated-functions).
+    ///
+    /// ```
+    /// use static_arc::StaticArc;
+    ///
+    /// struct Opaque;
+    ///
+    /// let [p1, _p2] = StaticArc::new(Opaque).unwrap();
+    /// assert_eq!(format!("{:?}", StaticArc::debug_live(&p1)), "StaticArc { live: 2, .. }");
+    /// ```
+    #[inline]
+    pub fn debug_live(this: &Self) -> impl fmt::Debug {
+        DebugLive(this.live())
+    }
+
+    /// An alias of [`StaticArc::live`], named after
+    /// `std::sync::Arc::strong_count`.
+    ///
+    /// Unlike with `Arc`, the count never grows past the number of owners
+    /// the allocation was created with, or later resplit into, since new
+    /// owners can't be cloned out of existing ones.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use static_arc::StaticArc;
+    ///
+    /// let a = Arc::new(5);
+    /// let _b = Arc::clone(&a);
+    /// assert_eq!(Arc::strong_count(&a), 2);
+    ///
+    /// let [p1, _p2] = StaticArc::new(5).unwrap();
+    /// assert_eq!(StaticArc::strong_count(&p1), 2);
+    /// ```
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        this.live().get()
+    }
+
+    /// Returns the number of owners the allocation was created with, or
+    /// last split into with [`StaticArc::resplit`] or [`StaticArc::split_at`].
+    ///
+    /// Unlike [`StaticArc::live`], this doesn't change as owners are
+    /// dropped, so `original_count - live` of them are gone, unless weak
+    /// references were upgraded in the meantime:
+    ///
+    /// ```
+    /// use static_arc::StaticArc;
+    ///
+    /// let [p1, p2, p3] = StaticArc::new(5).unwrap();
+    /// drop((p2, p3));
+    /// assert_eq!(StaticArc::original_count(&p1) - p1.live().get(), 2);
+    /// ```
+    #[inline]
+    pub fn original_count(this: &Self) -> usize {
+        this.arc().original.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of [`StaticWeak`] references to the shared
+    /// value, like `std::sync::Arc::weak_count`.
+    ///
+    /// Weak references keep the allocation alive, but not the value:
+    /// once the last owner is dropped, the value is dropped too, and
+    /// they fail to upgrade, while the allocation is only freed once
+    /// the last weak reference is gone.
+    #[inline]
+    pub fn weak_count(this: &Self) -> usize {
+        match this.arc().weak.load(Ordering::Relaxed) {
+            // locked by `is_unique` on the only owner, which
+            // only happens while no weak references exist
+            usize::MAX => 0,
+            // without the one held by the strong owners
+            count => count - 1,
+        }
+    }
+
+    /// Creates a [`StaticWeak`] reference to the shared value.
+    ///
+    /// While weak references exist, the value can't be mutated
+    /// through [`StaticArc::get_mut`], since they might be
+    /// upgraded at any time.
+    pub fn downgrade(&self) -> StaticWeak<T, A> {
+        self.acquire_weak(1);
+        StaticWeak { inner: self.inner }
+    }
+
+    /// Converts every owner into a [`StaticWeak`] reference, e.g. to
+    /// observe the end of a teardown without keeping the value alive.
+    ///
+    /// The weak count is incremented once for all the owners, before
+    /// they are dropped; the value is dropped as well, if they were
+    /// the last ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the owners don't share the same allocation.
+    pub fn downgrade_all<const N: usize>(owners: [Self; N]) -> [StaticWeak<T, A>; N] {
+        assert_owners::<N>();
+
+        let inner = owners[0].inner;
+        for owner in &owners[1..] {
+            assert!(StaticArc::ptr_eq(&owners[0], owner), "owners of different allocations");
+        }
+
+        owners[0].acquire_weak(N);
+        drop(owners);
+        [(); N].map(|()| StaticWeak { inner })
+    }
+
+    /// Adds `n` weak references to the allocation; converting
+    /// them into `StaticWeak`s is up to the caller.
+    fn acquire_weak(&self, n: usize) {
+        let weak = &self.arc().weak;
+        let mut current = weak.load(Ordering::Relaxed);
+
+        loop {
+            // the weak count is locked by `is_unique`
+            if current == usize::MAX {
+                sync::spin_loop();
+                current = weak.load(Ordering::Relaxed);
+                continue;
+            }
+
+            if current > MAX_REFCOUNT - n {
+                abort();
+            }
+
+            // acquire, to synchronize with the release
+            // that unlocks the weak count in `is_unique`
+            match weak.compare_exchange_weak(
+                current,
+                current + n,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(old) => current = old,
+            }
+        }
+    }
+
+    /// Returns `true` if exactly one other owner is alive, e.g. to start
+    /// a finalization handshake with it before reclaiming the value.
+    ///
+    /// This is inherently racy: the other owner may be dropped right
+    /// after the check, or a weak reference upgraded, so the result is
+    /// only a hint, unlike [`StaticArc::is_unique`].
+    #[inline]
+    pub fn is_penultimate(this: &Self) -> bool {
+        this.live().get() == 2
+    }
+
+    /// Returns `true` if this is the only owner left, and no weak
+    /// references exist, which is when [`StaticArc::get_mut`] succeeds.
+    ///
+    /// The result can't be known statically, even for allocations split
+    /// into a single owner: weak references may be upgraded at any time,
+    /// and [`StaticArc::resplit`] adds owners to an existing allocation.
+    /// Other owners being dropped concurrently may also make this return
+    /// `false` spuriously, but not `true`.
+    #[inline]
+    pub fn is_unique(this: &Self) -> bool {
+        this.arc().is_unique()
+    }
+
+    /// Registers a callback to run right before the shared value
+    /// is dropped, with mutable access to it.
+    ///
+    /// Only the most recently registered callback runs; registering
+    /// another one drops the previous callback without running it.
+    /// The callback doesn't run if the value is moved out with
+    /// [`StaticArc::try_into_inner_recover`] or similar.
+    pub fn on_last_drop<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        let value = ValuePtr(self.value_ptr());
+        let f: Box<dyn FnOnce() + Send + '_> = Box::new(move || {
+            // SAFETY: the callback only runs right before the
+            // value is dropped, by the last owner
+            f(unsafe { value.into_mut() })
+        });
+
+        // SAFETY: the callback runs or gets dropped while the value is
+        // still alive, so whatever `T` borrows outlives it
+        let f: OnLastDrop = unsafe { mem::transmute(f) };
+        let f = Box::into_raw(Box::new(f));
+
+        // release the callback to the last owner, and acquire the
+        // previous one, so that we can drop it
+        let previous = self.arc().on_last_drop.swap(f, Ordering::AcqRel);
+
+        if !previous.is_null() {
+            // SAFETY: non-null pointers were leaked from
+            // a `Box` above, and we swapped it out
+            drop(unsafe { Box::from_raw(previous) });
+        }
+    }
+
+    /// Returns a mutable reference to the shared value, if this is
+    /// the only owner left and no weak references exist, like
+    /// `std::sync::Arc::get_mut`.
+    ///
+    /// Borrowing the owner mutably ensures the returned reference
+    /// can't alias a shared one obtained through `Deref`. This is an
+    /// [associated function](StaticArc#associated-functions).
+    ///
+    /// Accesses through owners dropped on other threads happen before
+    /// the mutable access, and the writes made through it happen before
+    /// any access through owners created afterwards, e.g. with
+    /// [`StaticArc::resplit`]: its counter update releases them to weak
+    /// references upgraded later on, while the new owners themselves
+    /// reach other threads through something that synchronizes anyway.
+    #[must_use = "`None` means the value is shared, and can't be mutated"]
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        this.arc().check();
+        if Self::is_unique(this) {
+            // SAFETY: we are the only owner left, and we hold
+            // a unique borrow of it
+            Some(unsafe { &mut *this.value_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a guard with mutable access to the shared value, if this
+    /// is the only owner left and no weak references exist.
+    ///
+    /// The guard borrows the owner mutably for as long as it is alive,
+    /// so no other guard or reference into the value can coexist with it:
+    ///
+    /// ```compile_fail
+    /// use static_arc::StaticArc;
+    ///
+    /// let [mut p1] = StaticArc::new_const::<1>(5);
+    /// let mut first = StaticArc::lock_unique(&mut p1).unwrap();
+    /// let mut second = StaticArc::lock_unique(&mut p1).unwrap();
+    /// *first += 1;
+    /// *second += 1;
+    /// ```
+    #[must_use = "`None` means the value is shared, and can't be mutated"]
+    #[inline]
+    pub fn lock_unique(this: &mut Self) -> Option<UniqueGuard<'_, T, A>> {
+        this.arc().check();
+        if Self::is_unique(this) {
+            Some(UniqueGuard { owner: this })
+        } else {
+            None
+        }
+    }
+
+    /// Runs `f` on the shared value, with mutable access, if this is the
+    /// only owner left and no weak references exist, returning its result.
+    ///
+    /// Scoping the access to `f` keeps the mutable reference from being
+    /// held past the uniqueness check by mistake.
+    #[inline]
+    pub fn with_mut<R, F>(this: &mut Self, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        Self::get_mut(this).map(f)
+    }
+
+    /// Runs `f` on the shared value, returning its result. This is the
+    /// shared counterpart of [`StaticArc::with_mut`].
+    #[inline]
+    pub fn with_ref<R, F>(this: &Self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(this)
+    }
+
+    /// Returns a mutable reference to the shared value, without
+    /// checking whether other owners exist, like
+    /// `std::sync::Arc::get_mut_unchecked`.
+    ///
+    /// With debug assertions enabled, this panics if other owners
+    /// are alive; in release builds no atomic operation is performed.
+    ///
+    /// # Safety
+    ///
+    /// For as long as the returned reference is alive, no other owner
+    /// may exist, nor may any weak reference be upgraded. Any other
+    /// owners must have been dropped before this call, such that their
+    /// drops happen before it, e.g. through [`StaticArc::live`] or
+    /// [`StaticArc::get_mut`] having observed a single owner.
+    #[inline]
+    pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
+        debug_assert_eq!(this.live().get(), 1, "other owners are alive");
+
+        // SAFETY: the caller guarantees we are the only owner
+        unsafe { &mut *this.value_ptr() }
+    }
+
+    /// Method form of [`StaticArc::get_mut`], which shadows methods
+    /// with the same name on `T`.
+    #[must_use = "`None` means the value is shared, and can't be mutated"]
+    #[inline]
+    #[deprecated(note = "use `StaticArc::get_mut` or `StaticArc::lock_unique` instead")]
+    pub fn try_as_ref_mut(&mut self) -> Option<&mut T> {
+        Self::get_mut(self)
+    }
+}
+
+// moving an owner never moves the shared value, as in `std::sync::Arc`
+impl<T: ?Sized, A: Allocator> Unpin for StaticArc<T, A> {}
+
+/// As with `std::sync::Arc`, owners only hand out shared references to
+/// the value, unless they are borrowed mutably, so a panic can't leave it
+/// broken unless `T` has interior mutability:
+///
+/// ```compile_fail
+/// use std::cell::RefCell;
+/// use std::panic::UnwindSafe;
+/// use static_arc::StaticArc;
+///
+/// fn assert_unwind_safe<U: UnwindSafe>() {}
+///
+/// assert_unwind_safe::<StaticArc<RefCell<i32>>>();
+/// ```
+impl<T: ?Sized + RefUnwindSafe, A: Allocator + UnwindSafe> UnwindSafe for StaticArc<T, A> {}
+
+impl<T: ?Sized + RefUnwindSafe, A: Allocator + RefUnwindSafe> RefUnwindSafe for StaticArc<T, A> {}
+
+#[cfg(feature = "coerce_unsized")]
+impl<T, U, A> core::ops::CoerceUnsized<StaticArc<U, A>> for StaticArc<T, A>
+where
+    T: ?Sized + core::marker::Unsize<U>,
+    U: ?Sized,
+    A: Allocator,
+{
+}
+
+impl<T: ?Sized, A: Allocator> Deref for StaticArc<T, A> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.arc().check();
+
+        // SAFETY: mutable access to the value is only handed out
+        // to a unique owner, through a unique borrow
+        unsafe { &*self.value_ptr() }
+    }
+}
+
+/// Compares the shared values, short-circuiting for owners of the same
+/// allocation, which are always equal; unlike `std::sync::Arc`, this
+/// applies even to values that aren't equal to themselves, like NaN.
+impl<T: ?Sized + PartialEq, A: Allocator> PartialEq for StaticArc<T, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        StaticArc::ptr_eq(self, other) || **self == **other
+    }
+}
+
+impl<T: ?Sized + Eq, A: Allocator> Eq for StaticArc<T, A> {}
+
+/// Compares the shared values, short-circuiting for owners of the same
+/// allocation, which are always equal, consistently with `PartialEq`.
+impl<T: ?Sized + PartialOrd, A: Allocator> PartialOrd for StaticArc<T, A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        if StaticArc::ptr_eq(self, other) {
+            return Some(core::cmp::Ordering::Equal);
+        }
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: ?Sized + Ord, A: Allocator> Ord for StaticArc<T, A> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        if StaticArc::ptr_eq(self, other) {
+            return core::cmp::Ordering::Equal;
+        }
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: ?Sized, A: Allocator> AsRef<T> for StaticArc<T, A> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Borrow<T> for StaticArc<T, A> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+/// Moves a boxed value into the single owner of a fresh allocation,
+/// like [`StaticArc::from_box`].
+///
+/// Coherence rules out converting into `[StaticArc<T>; 1]`, as well as
+/// from an owner into a `Box<T>`, since neither type is local to this
+/// crate; [`StaticArc::into_box`] covers the latter.
+impl<T: ?Sized> From<Box<T>> for StaticArc<T> {
+    #[inline]
+    fn from(value: Box<T>) -> Self {
+        let Some([arc]) = Self::from_box(value) else {
+            unreachable!("a single owner is always valid");
+        };
+        arc
+    }
+}
+
+/// Treats an owner as pinned, which pinning APIs may require even though
+/// `T` doesn't care, like `Pin::new`; unlike [`StaticArc::pin`], this
+/// works for owners that already exist.
+impl<T: ?Sized + Unpin, A: Allocator> From<StaticArc<T, A>> for Pin<StaticArc<T, A>> {
+    #[inline]
+    fn from(owner: StaticArc<T, A>) -> Self {
+        Pin::new(owner)
+    }
+}
+
+impl<T: ?Sized + Hash, A: Allocator> Hash for StaticArc<T, A> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+/// Shows the number of live owners along with the shared value.
+///
+/// The count of a value which isn't `Debug` can still be shown
+/// through a [`StaticWeak`] reference.
+/// Returned by [`StaticArc::debug_live`].
+struct DebugLive(NonZeroUsize);
+
+impl fmt::Debug for DebugLive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticArc")
+            .field("live", &self.0)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: ?Sized + fmt::Debug, A: Allocator> fmt::Debug for StaticArc<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticArc")
+            .field("live", &self.live())
+            .field("value", &&**self)
+            .finish()
+    }
+}
+
+// formatting is forwarded to the shared value
+macro_rules! forward_fmt {
+    ($($trait:ident),*) => {
+        $(
+            impl<T: ?Sized + fmt::$trait, A: Allocator> fmt::$trait for StaticArc<T, A> {
+                #[inline]
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::$trait::fmt(&**self, f)
+                }
+            }
+        )*
+    };
+}
+
+forward_fmt!(Display, LowerHex, UpperHex, Binary, Octal);
+
+/// Formats the address of the shared value, which is the same
+/// for every owner of the allocation.
+impl<T: ?Sized, A: Allocator> fmt::Pointer for StaticArc<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&self.as_ptr(), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: ?Sized + serde::Serialize, A: Allocator> serde::Serialize for StaticArc<T, A> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+/// Deserializes a single owner of a fresh allocation.
+///
+/// Owners of the same allocation are serialized as independent copies
+/// of the shared value, so deserializing them doesn't share the value
+/// again: each one ends up in its own allocation.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for StaticArc<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [arc] = Self::new_unchecked(T::deserialize(deserializer)?);
+        Ok(arc)
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Drop for StaticArc<T, A> {
+    fn drop(&mut self) {
+        // unless we were the last owner, we are done
+        if Self::decrement(self) != 1 {
+            return;
+        }
+
+        // SAFETY: counter value reached 0, therefore
+        // no more `StaticArc` instances are alive
+        unsafe { StaticArcInner::drop_value(self.inner) };
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::prelude::rust_2021::*;
+    use std::vec;
+
+    use core::cell::Cell;
+
+    #[cfg(feature = "std")]
+    use std::sync::Mutex;
+
+    // large enough for allocations aligned to a cache line
+    #[repr(C, align(128))]
+    struct Bump {
+        memory: UnsafeCell<[u8; 1024]>,
+        next: Cell<usize>,
+        allocs: Cell<usize>,
+        deallocs: Cell<usize>,
+    }
+
+    impl Bump {
+        fn new() -> Self {
+            Bump {
+                memory: UnsafeCell::new([0; 1024]),
+                next: Cell::new(0),
+                allocs: Cell::new(0),
+                deallocs: Cell::new(0),
+            }
+        }
+    }
+
+    unsafe impl Allocator for Bump {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            let start = self.next.get().next_multiple_of(layout.align());
+            let end = start + layout.size();
+            if layout.align() > 128 || end > 1024 {
+                return Err(AllocError);
+            }
+            self.next.set(end);
+            self.allocs.set(self.allocs.get() + 1);
+            let ptr = unsafe { self.memory.get().cast::<u8>().add(start) };
+            let ptr = NonNull::new(ptr).unwrap();
+            Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+        }
+
+        unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+            self.deallocs.set(self.deallocs.get() + 1);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[allow(deprecated)]
+    fn test_ref_mut() {
+        let [mut p1, p2, p3, p4] = StaticArc::new(Mutex::new(1234)).unwrap();
+        std::thread::spawn(move || {
+            drop((p2, p3));
+        });
+        assert_eq!(*p4.lock().unwrap(), 1234);
+        drop(p4);
+        loop {
+            if let Some(p) = p1.try_as_ref_mut() {
+                *p.get_mut().unwrap() = 420;
+                break;
+            }
+        }
+        let x = p1.try_into_inner().unwrap();
+        assert_eq!(*x.lock().unwrap(), 420);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_share_scoped() {
+        let [p1, p2] = StaticArc::new(AtomicUsize::new(0)).unwrap();
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| p1.fetch_add(1, Ordering::SeqCst));
+            }
+        });
+        assert_eq!(p2.load(Ordering::SeqCst), 4);
+        drop(p2);
+        assert_eq!(p1.try_into_inner().unwrap().into_inner(), 4);
+    }
+
+    #[test]
+    fn test_deref_then_mutate() {
+        let [mut p1, p2] = StaticArc::new(0).unwrap();
+        let r1 = &*p1;
+        let r2 = &*p2;
+        assert_eq!(r1, r2);
+        assert!(StaticArc::get_mut(&mut p1).is_none());
+        drop(p2);
+        *StaticArc::get_mut(&mut p1).unwrap() = 1;
+        assert_eq!(*p1, 1);
+        *StaticArc::get_mut(&mut p1).unwrap() += 1;
+        assert_eq!(p1.try_into_inner(), Some(2));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_deref_sibling_then_mutate() {
+        let [mut p1, p2] = StaticArc::new(vec![1, 2, 3]).unwrap();
+        let sum = std::thread::spawn(move || p2.iter().sum::<i32>())
+            .join()
+            .unwrap();
+        assert_eq!(sum, 6);
+        let first = p1[0];
+        StaticArc::get_mut(&mut p1).unwrap().push(first);
+        assert_eq!(*p1, [1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_weak_upgrade() {
+        let [mut p1, p2] = StaticArc::new(String::from("abc")).unwrap();
+        let w = p1.downgrade();
+        assert_eq!(w.live(), 2);
+
+        // weak references prevent mutable access
+        drop(p2);
+        assert!(StaticArc::get_mut(&mut p1).is_none());
+
+        let p3 = w.upgrade().unwrap();
+        assert_eq!(*p3, "abc");
+        assert_eq!(p1.live().get(), 2);
+        drop(p3);
+
+        // the weak reference can't be upgraded after reclaiming
+        assert_eq!(p1.try_into_inner().unwrap(), "abc");
+        assert_eq!(w.live(), 0);
+        assert!(w.upgrade().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_weak_outlives_owners() {
+        let [p1, p2, p3] = StaticArc::new(Mutex::new(0)).unwrap();
+        let w = p1.downgrade();
+        let handles: Vec<_> = [p1, p2, p3]
+            .into_iter()
+            .map(|p| std::thread::spawn(move || *p.lock().unwrap() += 1))
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert!(w.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_ptr_eq() {
+        let [a1, a2, a3] = StaticArc::new(7).unwrap();
+        let [b1, b2, b3] = StaticArc::new(7).unwrap();
+        assert!(StaticArc::ptr_eq(&a1, &a3));
+        assert!(StaticArc::ptr_eq(&a2, &a1));
+        assert!(StaticArc::ptr_eq(&b3, &b2));
+        assert!(!StaticArc::ptr_eq(&a1, &b1));
+        assert_eq!(*a1, *b1);
+
+        let [c] = StaticArc::new(7).unwrap();
+        assert!(StaticArc::ptr_eq(&c, &c));
+        assert!(!StaticArc::ptr_eq(&c, &a2));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_raw_roundtrip() {
+        let [p1, p2] = StaticArc::new(String::from("raw")).unwrap();
+        let addr = p2.into_raw() as usize;
+        assert_eq!(p1.live().get(), 2);
+
+        let p2 = std::thread::spawn(move || {
+            // SAFETY: `addr` was returned by `into_raw`
+            let p2 = unsafe { StaticArc::from_raw(addr as *const String) };
+            assert_eq!(*p2, "raw");
+            assert_eq!(p2.live().get(), 2);
+            p2
+        })
+        .join()
+        .unwrap();
+
+        assert!(StaticArc::ptr_eq(&p1, &p2));
+        drop(p2);
+        assert_eq!(p1.live().get(), 1);
+        assert_eq!(p1.try_into_inner().unwrap(), "raw");
+    }
+
+    #[test]
+    fn test_as_ptr() {
+        let owners: [_; 3] = StaticArc::new([0u8; 16]).unwrap();
+        let ptr = owners[0].as_ptr();
+        assert_eq!(ptr, &*owners[0] as *const [u8; 16]);
+        assert!(owners.iter().all(|p| p.as_ptr() == ptr));
+        assert!(owners.iter().all(|p| p.live().get() == 3));
+    }
+
+    #[test]
+    fn test_new_with() {
+        let calls = std::cell::Cell::new(0);
+        let make = || {
+            calls.set(calls.get() + 1);
+            assert_eq!(calls.get(), 1, "called more than once");
+            vec![1, 2, 3]
+        };
+
+        assert!(StaticArc::new_with::<0, _>(make).is_none());
+        assert_eq!(calls.get(), 0);
+
+        let [p1, p2] = StaticArc::new_with(make).unwrap();
+        assert_eq!(calls.get(), 1);
+        assert_eq!(*p1, *p2);
+    }
+
+    #[test]
+    fn test_new_in() {
+        let bump = Bump::new();
+
+        let [p1, p2] = StaticArc::new_in(String::from("bump"), &bump).unwrap();
+        assert_eq!(*p1, "bump");
+        assert_eq!(bump.allocs.get(), 1);
+        drop(p1);
+        let w = p2.downgrade();
+        drop(p2);
+        assert_eq!(bump.deallocs.get(), 0);
+        drop(w);
+        assert_eq!(bump.deallocs.get(), 1);
+
+        let [p] = StaticArc::new_in(1234, &bump).unwrap();
+        assert_eq!(p.try_into_inner(), Some(1234));
+        assert!(StaticArc::<i32, _>::new_in::<0>(1, &bump).is_none());
+        assert_eq!(bump.allocs.get(), 2);
+        assert_eq!(bump.deallocs.get(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_roundtrip() {
+        let [p1, p2] = StaticArc::new(String::from("shared")).unwrap();
+        let json = serde_json::to_string(&[&p1, &p2]).unwrap();
+        assert_eq!(json, r#"["shared","shared"]"#);
+
+        let [q1, q2]: [StaticArc<String>; 2] = serde_json::from_str(&json).unwrap();
+        assert_eq!(*q1, *p1);
+        assert_eq!(q1.live().get(), 1);
+        assert!(!StaticArc::ptr_eq(&q1, &q2));
+
+        let q: StaticArc<String> = serde_json::from_str(r#""single""#).unwrap();
+        assert_eq!(q.try_into_inner().unwrap(), "single");
+    }
+
+    #[test]
+    fn test_from_box_slice() {
+        let boxed: Box<[i32]> = vec![1, 2, 3].into_boxed_slice();
+        let [mut p1, p2] = StaticArc::from_box(boxed).unwrap();
+        assert_eq!(*p1, [1, 2, 3]);
+        assert_eq!(p2.len(), 3);
+        assert!(StaticArc::get_mut(&mut p1).is_none());
+        drop(p2);
+        StaticArc::get_mut(&mut p1).unwrap()[0] = 4;
+        assert_eq!(*p1, [4, 2, 3]);
+
+        let empty: Box<[u64]> = Box::new([]);
+        let [e] = StaticArc::from_box(empty).unwrap();
+        assert!(e.is_empty());
+    }
+
+    #[test]
+    fn test_from_box_dyn() {
+        let value = String::from("dyn");
+        let boxed: Box<dyn Fn() -> usize + Send + Sync> = Box::new(move || value.len());
+        let [p1, p2] = StaticArc::from_box(boxed).unwrap();
+        let w = p1.downgrade();
+        assert_eq!(p1(), 3);
+        assert_eq!(p2(), 3);
+        assert!(StaticArc::ptr_eq(&p1, &p2));
+        drop(p1);
+
+        // SAFETY: the pointer was returned by `into_raw`
+        let p2 = unsafe { StaticArc::from_raw(p2.into_raw()) };
+        assert_eq!(p2(), 3);
+        drop(p2);
+        assert!(w.upgrade().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "coerce_unsized")]
+    fn test_coerce_unsized() {
+        use core::fmt::Display;
+
+        let [p1, p2] = StaticArc::new(5i32).unwrap();
+        let [q1] = StaticArc::new(String::from("five")).unwrap();
+        let w = p2.downgrade();
+        q1.on_last_drop(|s| s.push('!'));
+
+        let p1: StaticArc<dyn Display> = p1;
+        assert_eq!(p1.live().get(), 2);
+
+        let owners: Vec<StaticArc<dyn Display>> = vec![p1, p2, q1];
+        let shown: Vec<_> = owners.iter().map(|p| p.to_string()).collect();
+        assert_eq!(shown, ["5", "5", "five"]);
+
+        drop(owners);
+        let w: StaticWeak<dyn Display> = w;
+        assert!(w.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_pin() {
+        struct SelfAddr {
+            addr: Cell<usize>,
+            _pin: core::marker::PhantomPinned,
+        }
+
+        impl SelfAddr {
+            fn check(self: Pin<&Self>) {
+                let addr = &*self as *const Self as usize;
+                if self.addr.get() == 0 {
+                    self.addr.set(addr);
+                }
+                assert_eq!(self.addr.get(), addr);
+            }
+        }
+
+        let value = SelfAddr {
+            addr: Cell::new(0),
+            _pin: core::marker::PhantomPinned,
+        };
+        let owners: [_; 3] = StaticArc::pin(value).unwrap();
+        for owner in &owners {
+            owner.as_ref().check();
+        }
+
+        // move the owners around
+        let moved: Vec<_> = owners.into_iter().rev().collect();
+        for owner in &moved {
+            owner.as_ref().check();
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "std", feature = "async"))]
+    #[cfg_attr(miri, ignore = "tokio leaks its runtime under miri")]
+    async fn test_into_inner_async() {
+        use std::time::Duration;
+
+        let [main, o1, o2, o3] = StaticArc::new(Mutex::new(Vec::new())).unwrap();
+        for (i, owner) in [o1, o2, o3].into_iter().enumerate() {
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(10 * i as u64)).await;
+                owner.lock().unwrap().push(i);
+            });
+        }
+
+        let mut values = main.into_inner_async().await.into_inner().unwrap();
+        values.sort();
+        assert_eq!(values, [0, 1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_into_inner_spin() {
+        use std::time::Duration;
+
+        let [main, o1, o2, o3] = StaticArc::new(Mutex::new(Vec::new())).unwrap();
+        for (i, owner) in [o1, o2, o3].into_iter().enumerate() {
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(10 * i as u64));
+                owner.lock().unwrap().push(i);
+            });
+        }
+
+        // count the spins before each check, and the yields
+        let mut backoff = backoff::Backoff::new();
+        let mut spins = Vec::new();
+        let mut yields = 0;
+        let value = main.into_inner_snoozing(|| {
+            let mut spun = 0;
+            backoff.snooze_with(
+                || {
+                    spun += 1;
+                    sync::spin_loop();
+                },
+                || {
+                    yields += 1;
+                    sync::yield_now();
+                },
+            );
+            if spun > 0 {
+                assert_eq!(yields, 0, "spun again after yielding");
+                spins.push(spun);
+            }
+        });
+
+        let mut values = value.into_inner().unwrap();
+        values.sort();
+        assert_eq!(values, [0, 1, 2]);
+
+        // spinning twice as long before each check, up to 64 spins, after
+        // which the thread only yields in between checks, however long
+        // the wait is
+        assert_eq!(spins, [1, 2, 4, 8, 16, 32, 64][..spins.len()]);
+    }
+
+    #[test]
+    #[cfg(feature = "park")]
+    fn test_into_inner_wait() {
+        use std::time::Duration;
+
+        let [main, o1, o2, o3] = StaticArc::new(Mutex::new(Vec::new())).unwrap();
+        for (i, owner) in [o1, o2, o3].into_iter().enumerate() {
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(100 * i as u64));
+                owner.lock().unwrap().push(i);
+            });
+        }
+
+        let mut values = main.into_inner_wait().into_inner().unwrap();
+        values.sort();
+        assert_eq!(values, [0, 1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_on_last_drop() {
+        use std::sync::Arc;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let [p1, p2, p3] = StaticArc::new(vec![1]).unwrap();
+
+        let first = Arc::clone(&seen);
+        p1.on_last_drop(move |_| first.lock().unwrap().push(0));
+        let last = Arc::clone(&seen);
+        p2.on_last_drop(move |v: &mut Vec<i32>| {
+            v.push(2);
+            last.lock().unwrap().extend(v.drain(..));
+        });
+
+        drop(p1);
+        drop(p2);
+        assert!(seen.lock().unwrap().is_empty());
+        drop(p3);
+        assert_eq!(*seen.lock().unwrap(), [1, 2]);
+        assert_eq!(Arc::strong_count(&seen), 1);
+    }
+
+    #[test]
+    fn test_map() {
+        let [p1, p2] = StaticArc::new((vec![1u8, 2, 3], 7u32)).unwrap();
+        let small = StaticArc::map(p1, |(_, small)| small);
+
+        assert_eq!(*small, 7);
+        assert_eq!(p2.live().get(), 2);
+        assert!(StaticArc::ptr_eq(StaticArcProjected::owner(&small), &p2));
+
+        let big = StaticArc::map(p2, |(big, _)| big);
+        drop(small);
+        let tail = StaticArcProjected::map(big, |big| &big[1..]);
+        assert_eq!(*tail, [2, 3]);
+        assert_eq!(StaticArcProjected::owner(&tail).live().get(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_new_dyn() {
+        let n = std::hint::black_box(5);
+        let mut owners = StaticArc::new_dyn(AtomicUsize::new(0), n).unwrap();
+        assert_eq!(owners.len(), n);
+
+        let main = owners.pop().unwrap();
+        let handles: Vec<_> = owners
+            .into_iter()
+            .map(|owner| std::thread::spawn(move || {
+                owner.fetch_add(1, Ordering::SeqCst);
+            }))
+            .collect();
+        handles.into_iter().for_each(|h| h.join().unwrap());
+
+        assert_eq!(main.try_into_inner().unwrap().into_inner(), n - 1);
+        assert!(StaticArc::new_dyn((), 0).is_none());
+    }
+
+    #[test]
+    fn test_resplit() {
+        let [p1, p2] = StaticArc::new(5).unwrap();
+        let addr = p1.as_ptr();
+
+        let p1 = p1.resplit::<4>().unwrap_err();
+        drop(p2);
+
+        let owners = p1.resplit::<4>().unwrap();
+        assert!(owners.iter().all(|p| p.as_ptr() == addr && p.live().get() == 4));
+
+        let [p1, p2, p3, p4] = owners;
+        drop((p2, p3, p4));
+        assert_eq!(p1.try_into_inner(), Some(5));
+    }
+
+    #[test]
+    fn test_into_std_arc() {
+        let [p1, p2] = StaticArc::new(vec![1, 2, 3]).unwrap();
+        let p1 = p1.into_std_arc().unwrap_err();
+        drop(p2);
+
+        let arc = p1.into_std_arc().unwrap();
+        let clones = [Arc::clone(&arc), Arc::clone(&arc)];
+        drop(arc);
+        assert!(clones.iter().all(|arc| **arc == [1, 2, 3]));
+    }
+
+    #[test]
+    fn test_into_box() {
+        #[derive(Debug)]
+        struct Counted<'a>(&'a Cell<usize>, [u64; 32]);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let [p1, p2] = StaticArc::new(Counted(&drops, [7; 32])).unwrap();
+        let p1 = p1.into_box().unwrap_err();
+        drop(p2);
+
+        let boxed = p1.into_box().unwrap();
+        assert_eq!(boxed.1, [7; 32]);
+        assert_eq!(drops.get(), 0);
+        drop(boxed);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_new_uninit() {
+        use std::sync::{Arc, Barrier};
+
+        let [writer, reader] = StaticArc::<Vec<u8>>::new_uninit();
+        let barrier = Arc::new(Barrier::new(2));
+
+        let handle = std::thread::spawn({
+            let barrier = Arc::clone(&barrier);
+            move || {
+                // SAFETY: we're the only writer, and the
+                // reader waits on the barrier
+                unsafe {
+                    writer.as_ptr().cast_mut().write(MaybeUninit::new(vec![1, 2]));
+                }
+                barrier.wait();
+                unsafe { StaticArc::assume_init(writer) }
+            }
+        });
+
+        barrier.wait();
+        // SAFETY: the value was written before the barrier
+        let reader = unsafe { StaticArc::assume_init(reader) };
+        assert_eq!(*reader, [1, 2]);
+
+        drop(handle.join().unwrap());
+        assert_eq!(reader.try_into_inner().unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let [mut p1, p2] = StaticArc::new(vec![1]).unwrap();
+        assert!(StaticArc::get_mut(&mut p1).is_none());
+        assert_eq!(p2.live().get(), 2);
+        drop(p2);
+
+        StaticArc::get_mut(&mut p1).unwrap().push(2);
+        assert_eq!(p1.try_into_inner().unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn test_get_mut_unchecked() {
+        let [mut p1] = StaticArc::new(1).unwrap();
+        // SAFETY: `p1` is the only owner
+        unsafe { *StaticArc::get_mut_unchecked(&mut p1) += 1 };
+        assert_eq!(*p1, 2);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "other owners are alive"]
+    fn test_get_mut_unchecked_shared() {
+        let [mut p1, _p2] = StaticArc::new(1).unwrap();
+        // the debug assertion fires before the reference is created
+        unsafe { StaticArc::get_mut_unchecked(&mut p1) };
+    }
+
+    #[test]
+    fn test_eq() {
+        let [a1, a2] = StaticArc::new(String::from("a")).unwrap();
+        let [b] = StaticArc::new(String::from("a")).unwrap();
+        let [c] = StaticArc::new(String::from("c")).unwrap();
+        assert_eq!(a1, b);
+        assert_ne!(a1, c);
+
+        let [nan1, nan2] = StaticArc::new(f64::NAN).unwrap();
+        let [nan3] = StaticArc::new(f64::NAN).unwrap();
+        assert_eq!(nan1, nan2);
+        assert_ne!(nan1, nan3);
+        assert_eq!(a1, a2);
+    }
+
+    #[test]
+    fn test_ord() {
+        use std::collections::BTreeSet;
+
+        let [b1, b2] = StaticArc::new(2).unwrap();
+        let [a] = StaticArc::new(1).unwrap();
+        let [c] = StaticArc::new(3).unwrap();
+        assert_eq!(b1.cmp(&b2), core::cmp::Ordering::Equal);
+
+        let mut owners = vec![c, b1, a, b2];
+        owners.sort();
+        assert_eq!(owners.iter().map(|p| **p).collect::<Vec<_>>(), [1, 2, 2, 3]);
+
+        let set: BTreeSet<_> = owners.into_iter().collect();
+        assert_eq!(set.len(), 3);
+        let [key] = StaticArc::new(2).unwrap();
+        assert!(set.contains(&key));
+
+        let [nan1, nan2] = StaticArc::new(f64::NAN).unwrap();
+        assert_eq!(nan1.partial_cmp(&nan2), Some(core::cmp::Ordering::Equal));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_hash() {
+        use std::collections::HashSet;
+
+        let [a1, a2] = StaticArc::new(String::from("a")).unwrap();
+        let [b] = StaticArc::new(String::from("b")).unwrap();
+        let set: HashSet<_> = [a1, a2, b].into_iter().collect();
+        assert_eq!(set.len(), 2);
+
+        let [a] = StaticArc::new(String::from("a")).unwrap();
+        assert!(set.contains(&a));
+    }
+
+    #[test]
+    fn test_fmt() {
+        let [s] = StaticArc::new(String::from("abc")).unwrap();
+        let [n] = StaticArc::new(255u32).unwrap();
+        assert_eq!(format!("{s:>5}"), "  abc");
+        assert_eq!(format!("{n:#x} {n:X} {n:b} {n:o}"), "0xff FF 11111111 377");
+    }
+
+    #[test]
+    fn test_fmt_pointer() {
+        let [a, b] = StaticArc::new(0u8).unwrap();
+        let [c] = StaticArc::new(0u8).unwrap();
+        assert_eq!(format!("{a:p}"), format!("{b:p}"));
+        assert_ne!(format!("{a:p}"), format!("{c:p}"));
+        assert_eq!(format!("{a:p}"), format!("{:p}", a.as_ptr()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_borrow() {
+        use std::collections::HashMap;
+
+        fn len(s: impl AsRef<str>) -> usize {
+            s.as_ref().len()
+        }
+
+        let [k1, k2] = StaticArc::<str>::from_box("key".into()).unwrap();
+        let map = HashMap::from([(k1, 1)]);
+        assert_eq!(map.get("key"), Some(&1));
+        assert_eq!(len(k2), 3);
+    }
+
+    #[test]
+    fn test_debug() {
+        struct Opaque;
+
+        let [p1, p2, p3] = StaticArc::new(vec![1]).unwrap();
+        drop(p2);
+        assert_eq!(format!("{p1:?}"), "StaticArc { live: 2, value: [1] }");
+        drop(p3);
+        assert_eq!(format!("{p1:?}"), "StaticArc { live: 1, value: [1] }");
+
+        let [o1, _o2] = StaticArc::new(Opaque).unwrap();
+        assert_eq!(format!("{:?}", o1.downgrade()), "StaticWeak { live: 2 }");
+        assert_eq!(format!("{:?}", StaticArc::debug_live(&o1)), "StaticArc { live: 2, .. }");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_zst() {
+        use core::mem::size_of;
+
+        assert_eq!(size_of::<StaticArc<()>>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<StaticArc<()>>>(), size_of::<usize>());
+
+        let bump = Bump::new();
+        let [token] = StaticArc::new_in((), &bump).unwrap();
+        let header = Layout::new::<StaticArcInner<(), &Bump>>();
+        assert_eq!(bump.next.get(), header.size());
+        assert_eq!(token.try_into_inner(), Some(()));
+
+        let [main, tokens @ ..] = StaticArc::<()>::new::<4>(()).unwrap();
+        let handles = tokens.map(|token| std::thread::spawn(move || drop(token)));
+        handles.into_iter().for_each(|h| h.join().unwrap());
+        assert_eq!(main.try_into_inner(), Some(()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_drop_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        struct Bomb;
+
+        impl Drop for Bomb {
+            fn drop(&mut self) {
+                std::panic!("boom");
+            }
+        }
+
+        let bump = Bump::new();
+        let [p1, p2] = StaticArc::new_in(Bomb, &bump).unwrap();
+        drop(p1);
+        assert!(catch_unwind(AssertUnwindSafe(|| drop(p2))).is_err());
+        assert_eq!(bump.deallocs.get(), 1);
+
+        // the value still gets dropped if the callback panics
+        let drops = Arc::new(());
+        let [p] = StaticArc::new_in(Arc::clone(&drops), &bump).unwrap();
+        p.on_last_drop(|_| std::panic!("callback"));
+        assert!(catch_unwind(AssertUnwindSafe(|| drop(p))).is_err());
+        assert_eq!(Arc::strong_count(&drops), 1);
+        assert_eq!(bump.deallocs.get(), 2);
+    }
+
+    #[test]
+    fn test_is_unique() {
+        let [p1, p2] = StaticArc::new(1).unwrap();
+        assert!(!StaticArc::is_unique(&p1));
+        drop(p2);
+        assert!(StaticArc::is_unique(&p1));
+
+        let w = p1.downgrade();
+        assert!(!StaticArc::is_unique(&p1));
+        drop(w);
+        assert!(StaticArc::is_unique(&p1));
+    }
+
+    #[test]
+    fn test_replace() {
+        let [mut p1, p2] = StaticArc::new(String::from("old")).unwrap();
+        assert_eq!(StaticArc::replace(&mut p1, "new".into()), Err("new".into()));
+        drop(p2);
+
+        assert_eq!(StaticArc::replace(&mut p1, "new".into()), Ok("old".into()));
+        assert_eq!(*p1, "new");
+        assert_eq!(p1.live().get(), 1);
+    }
+
+    #[test]
+    fn test_take() {
+        let [mut p1, p2] = StaticArc::new(vec![1u8, 2]).unwrap();
+        assert_eq!(StaticArc::take(&mut p1), None);
+        assert_eq!(*p2, [1, 2]);
+        drop(p2);
+
+        assert_eq!(StaticArc::take(&mut p1), Some(vec![1, 2]));
+        assert!(p1.is_empty());
+    }
+
+    #[test]
+    fn test_new_const() {
+        let [p1, p2] = StaticArc::new_const(1);
+        assert!(StaticArc::ptr_eq(&p1, &p2));
+        assert_eq!(p1.live().get(), 2);
+    }
+
+    #[test]
+    fn test_live_relaxed() {
+        let [p1, p2, p3] = StaticArc::new(()).unwrap();
+        assert_eq!(p1.live_relaxed().get(), 3);
+        drop((p2, p3));
+        assert_eq!(p1.live_relaxed(), p1.live());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_gather() {
+        let [worker, o1, o2] = StaticArc::new(Mutex::new(Vec::new())).unwrap();
+        std::thread::spawn(move || worker.lock().unwrap().push(1));
+
+        let values = StaticArc::gather([o1, o2]).into_inner().unwrap();
+        assert_eq!(values, [1]);
+        assert_eq!(StaticArc::gather(StaticArc::new_const::<1>(2)), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "owners of different allocations")]
+    fn test_gather_different() {
+        let [p1] = StaticArc::new(1).unwrap();
+        let [p2] = StaticArc::new(1).unwrap();
+        StaticArc::gather([p1, p2]);
+    }
+
+    #[test]
+    fn test_ffi() {
+        use core::ffi::c_void;
+
+        let drops = Arc::new(());
+        let addref: unsafe extern "C" fn(*const c_void) = ffi::static_arc_addref::<Arc<()>>;
+        let release: unsafe extern "C" fn(*const c_void) = ffi::static_arc_release::<Arc<()>>;
+
+        let [p1, p2] = StaticArc::new(Arc::clone(&drops)).unwrap();
+        let ptr = p1.into_raw().cast::<c_void>();
+        unsafe { addref(ptr) };
+        assert_eq!(p2.live().get(), 3);
+
+        drop(p2);
+        unsafe { release(ptr) };
+        assert_eq!(Arc::strong_count(&drops), 2);
+        unsafe { release(ptr) };
+        assert_eq!(Arc::strong_count(&drops), 1);
+    }
+
+    #[test]
+    fn test_try_new() {
+        struct Fail;
+
+        unsafe impl Allocator for Fail {
+            fn allocate(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                Err(AllocError)
+            }
+
+            unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+                unreachable!()
+            }
+        }
+
+        let (value, AllocError) = StaticArc::try_new_in::<2>(vec![1], Fail).unwrap_err();
+        assert_eq!(value, [1]);
+
+        let [p1, p2] = StaticArc::try_new(value).unwrap();
+        assert_eq!(p2.live().get(), 2);
+        drop(p2);
+        assert_eq!(p1.try_into_inner().unwrap(), [1]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_unwind_safe() {
+        let [p1, p2] = StaticArc::new(String::from("abc")).unwrap();
+        let weak = p1.downgrade();
+        let len = std::panic::catch_unwind(move || p1.len()).unwrap();
+        assert_eq!(len, 3);
+        assert!(std::panic::catch_unwind(|| weak.upgrade().is_some()).unwrap());
+        drop(p2);
+    }
+
+    #[test]
+    fn test_parts() {
+        let owners = StaticArc::new_const::<3>(String::from("parts"));
+        let table: Vec<(usize, usize)> = owners
+            .into_iter()
+            .map(|owner| {
+                let (ptr, token) = owner.into_parts();
+                (ptr.as_ptr() as usize, token)
+            })
+            .collect();
+
+        let mut owners: Vec<StaticArc<String>> = table
+            .into_iter()
+            .map(|(addr, token)| unsafe {
+                StaticArc::from_parts(NonNull::new(addr as *mut ()).unwrap(), token)
+            })
+            .collect();
+
+        assert_eq!(owners[0].live().get(), 3);
+        let last = owners.pop().unwrap();
+        drop(owners);
+        assert_eq!(last.try_into_inner().unwrap(), "parts");
+    }
+
+    #[test]
+    fn test_by_address() {
+        use std::collections::HashMap;
+
+        let [p1, p2] = StaticArc::new_const::<2>(5);
+        let [q1] = StaticArc::new_const::<1>(5);
+        assert_eq!(p1, q1);
+
+        let mut map = HashMap::new();
+        *map.entry(p1.by_address()).or_insert(0) += 1;
+        *map.entry(p2.by_address()).or_insert(0) += 1;
+        *map.entry(q1.by_address()).or_insert(0) += 1;
+
+        assert_eq!(map.len(), 2);
+        let mut counts: Vec<_> = map.into_values().collect();
+        counts.sort();
+        assert_eq!(counts, [1, 2]);
+    }
+
+    #[test]
+    fn test_try_into_inner_detailed() {
+        let [p1, p2, p3, p4] = StaticArc::new_const::<4>(5);
+
+        let (p1, live) = p1.try_into_inner_detailed().unwrap_err();
+        assert_eq!(live, 4);
+
+        drop(p2);
+        drop(p3);
+        let (p1, live) = p1.try_into_inner_detailed().unwrap_err();
+        assert_eq!(live, 2);
+
+        drop(p4);
+        assert_eq!(p1.try_into_inner_detailed().ok(), Some(5));
+    }
+
+    #[test]
+    fn test_with_mut() {
+        let [mut p1, p2] = StaticArc::new_const::<2>(vec![1]);
+        assert_eq!(StaticArc::with_mut(&mut p1, |v| v.push(2)), None);
+        assert_eq!(StaticArc::with_ref(&p2, |v| v.len()), 1);
+
+        drop(p2);
+        assert_eq!(StaticArc::with_mut(&mut p1, |v| { v.push(2); v.len() }), Some(2));
+        assert_eq!(StaticArc::with_ref(&p1, |v| v.clone()), [1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_split_at() {
+        let [p1, p2] = StaticArc::new_const::<2>(String::from("split"));
+        let p1 = p1.split_at::<2, 3>().unwrap_err();
+        drop(p2);
+
+        let ([a1, a2], [b1, b2, b3]) = p1.split_at::<2, 3>().unwrap();
+        assert_eq!(a1.live().get(), 5);
+        assert!(StaticArc::ptr_eq(&a2, &b3));
+
+        let b1 = std::thread::spawn(move || {
+            drop((a1, b2));
+            b1
+        }).join().unwrap();
+        drop((a2, b3));
+        assert_eq!(b1.into_inner_spin(), "split");
+    }
+
+    #[test]
+    fn test_make_owned() {
+        let [p1, p2] = StaticArc::new_const::<2>(vec![1, 2]);
+
+        let mut owned = StaticArc::make_owned(&p1);
+        owned.push(3);
+        assert_eq!(*p1, [1, 2]);
+
+        let buffer = Vec::as_ptr(&p1);
+        let mut owned = StaticArc::make_owned_cheap(p1);
+        assert_ne!(owned.as_ptr(), buffer, "the value is cloned");
+        owned.push(3);
+        assert_eq!(*p2, [1, 2]);
+
+        let buffer = Vec::as_ptr(&p2);
+        let owned = StaticArc::make_owned_cheap(p2);
+        assert_eq!(owned.as_ptr(), buffer, "the value is moved");
+        assert_eq!(owned, [1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "park")]
+    fn test_into_inner_timeout() {
+        use std::time::Duration;
+
+        let [p1, p2] = StaticArc::new_const::<2>(5);
+        let slow = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            drop(p2);
+        });
+        let p1 = p1.into_inner_timeout(Duration::from_millis(10)).unwrap_err();
+        slow.join().unwrap();
+        drop(p1);
+
+        let [p1, p2] = StaticArc::new_const::<2>(5);
+        let quick = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(10));
+            drop(p2);
+        });
+        assert_eq!(p1.into_inner_timeout(Duration::from_secs(10)).ok(), Some(5));
+        quick.join().unwrap();
+    }
+
+    #[test]
+    fn test_new_zeroed() {
+        let [p1, p2] = StaticArc::<[u8; 1 << 16]>::new_zeroed::<2>().unwrap();
+        drop(p2);
+
+        // SAFETY: zeroed bytes are valid `u8`s
+        let p1 = unsafe { StaticArc::assume_init(p1) };
+        assert!(p1.iter().all(|&b| b == 0));
+        assert!(StaticArc::<u8>::new_zeroed::<0>().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_owner_set() {
+        use std::sync::mpsc;
+
+        let mut set = OwnerSet::from(StaticArc::new_const::<5>(Mutex::new(0)));
+        let keep = set.take_one().unwrap();
+        assert_eq!(set.len(), 4);
+
+        let (tx, rx) = mpsc::channel::<StaticArc<Mutex<i32>>>();
+        let rx = Arc::new(Mutex::new(rx));
+        let pool: Vec<_> = (0..2)
+            .map(|_| {
+                let rx = Arc::clone(&rx);
+                std::thread::spawn(move || loop {
+                    let owner = rx.lock().unwrap().recv();
+                    match owner {
+                        Ok(owner) => *owner.lock().unwrap() += 1,
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+
+        set.distribute(|owner| tx.send(owner).unwrap());
+        drop(tx);
+        for worker in pool {
+            worker.join().unwrap();
+        }
+        assert_eq!(keep.into_inner_spin().into_inner().unwrap(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_zip() {
+        let [p1, p2] = StaticArc::zip::<_, 2>(String::from("zip"), 3_u8).unwrap();
+        let len = std::thread::spawn(move || p2.0.len() + usize::from(p2.1))
+            .join()
+            .unwrap();
+        assert_eq!(len, 6);
+        assert_eq!(p1.try_into_inner().unwrap(), (String::from("zip"), 3));
+
+        let [a1, a2] = StaticArc::new_const::<2>(String::from("zip"));
+        let [b] = StaticArc::new_const::<1>(3_u8);
+        let (a1, b) = StaticArc::zip_existing::<_, 2>(a1, b).unwrap_err();
+        drop(a2);
+
+        let weak = b.downgrade();
+        let (a1, b) = StaticArc::zip_existing::<_, 2>(a1, b).unwrap_err();
+        drop(weak);
+
+        let [p1, p2] = StaticArc::zip_existing::<_, 2>(a1, b).unwrap();
+        std::thread::spawn(move || assert_eq!(p2.1, 3)).join().unwrap();
+        assert_eq!(p1.try_into_inner().unwrap(), (String::from("zip"), 3));
+    }
+
+    #[test]
+    fn test_map_value() {
+        let [p1, p2] = StaticArc::new_const::<2>(String::from("map"));
+        let p1 = StaticArc::map_value::<_, 3, _>(p1, |s| s.len()).unwrap_err();
+        drop(p2);
+
+        let [l1, l2, l3] = StaticArc::map_value(p1, |s| s.len()).unwrap();
+        assert_eq!((*l1, *l2, *l3), (3, 3, 3));
+        assert_eq!(l1.live().get(), 3);
+    }
+
+    /// Counts how many times it's been woken.
+    #[cfg(all(feature = "std", feature = "async"))]
+    struct CountWakes(core::sync::atomic::AtomicUsize);
+
+    #[cfg(all(feature = "std", feature = "async"))]
+    impl std::task::Wake for CountWakes {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "async"))]
+    fn test_poll_unique() {
+        use std::sync::atomic::Ordering;
+        use std::task::Waker;
+
+        let wakes = Arc::new(CountWakes(Default::default()));
+        let waker = Waker::from(Arc::clone(&wakes));
+        let mut cx = Context::from_waker(&waker);
+
+        let [p1, p2, p3] = StaticArc::new_const::<3>(5);
+        assert!(StaticArc::poll_unique(&p1, &mut cx).is_pending());
+
+        drop(p3);
+        assert_eq!(wakes.0.load(Ordering::Relaxed), 0);
+        std::thread::spawn(move || drop(p2)).join().unwrap();
+        assert_eq!(wakes.0.load(Ordering::Relaxed), 1);
+
+        assert!(StaticArc::poll_unique(&p1, &mut cx).is_ready());
+        assert_eq!(p1.try_into_inner(), Some(5));
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "async"))]
+    fn test_into_inner_async_wakers() {
+        use core::future::Future;
+        use std::task::Waker;
+
+        let w1 = Arc::new(CountWakes(Default::default()));
+        let w2 = Arc::new(CountWakes(Default::default()));
+        let (waker1, waker2) = (Waker::from(Arc::clone(&w1)), Waker::from(Arc::clone(&w2)));
+
+        let [p1, p2, p3] = StaticArc::new_const::<3>(5);
+        let mut future = Box::pin(p1.into_inner_async());
+        for _ in 0..3 {
+            assert!(future.as_mut().poll(&mut Context::from_waker(&waker1)).is_pending());
+        }
+        assert_eq!(Arc::strong_count(&w1), 3);
+
+        // a future polled from another task swaps its waker
+        assert!(future.as_mut().poll(&mut Context::from_waker(&waker2)).is_pending());
+        assert_eq!((Arc::strong_count(&w1), Arc::strong_count(&w2)), (2, 3));
+
+        // and takes it out when dropped early
+        drop(future);
+        assert_eq!(Arc::strong_count(&w2), 2);
+
+        drop(p3);
+        assert_eq!(w2.0.load(core::sync::atomic::Ordering::Relaxed), 0);
+        assert_eq!(p2.try_into_inner(), Some(5));
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "async"))]
+    #[cfg_attr(miri, ignore = "miri doesn't deduplicate the vtables compared by `Waker::will_wake`")]
+    fn test_poll_unique_wakers() {
+        use std::task::Waker;
+
+        let wakes = Arc::new(CountWakes(Default::default()));
+        let waker = Waker::from(Arc::clone(&wakes));
+        let mut cx = Context::from_waker(&waker);
+
+        let [p1, p2] = StaticArc::new_const::<2>(5);
+        for _ in 0..3 {
+            assert!(StaticArc::poll_unique(&p1, &mut cx).is_pending());
+        }
+        assert_eq!(Arc::strong_count(&wakes), 3);
+
+        drop(p2);
+        assert_eq!(wakes.0.load(core::sync::atomic::Ordering::Relaxed), 1);
+        assert_eq!(Arc::strong_count(&wakes), 2);
+
+        // polling once ready leaves no waker behind
+        assert!(StaticArc::poll_unique(&p1, &mut cx).is_ready());
+        assert_eq!(Arc::strong_count(&wakes), 2);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "the allocation is leaked on purpose")]
+    fn test_leak() {
+        let [p1, p2, p3] = StaticArc::new_const::<3>(String::from("leak"));
+        drop(p3);
+
+        let leaked: &'static String = {
+            let p2 = p2;
+            StaticArc::leak(p2)
+        };
+        assert_eq!(p1.live().get(), 2);
+
+        drop(p1);
+        assert_eq!(leaked, "leak");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_assume_unique() {
+        use std::sync::Barrier;
+
+        let barrier = Arc::new(Barrier::new(3));
+        let [p1, p2, p3] = StaticArc::new_const::<3>(String::from("unique"));
+        let threads: Vec<_> = [p2, p3]
+            .into_iter()
+            .map(|owner| {
+                let barrier = Arc::clone(&barrier);
+                std::thread::spawn(move || {
+                    drop(owner);
+                    barrier.wait();
+                })
+            })
+            .collect();
+
+        barrier.wait();
+        // SAFETY: the other owners were dropped before the barrier
+        assert_eq!(unsafe { p1.assume_unique() }, "unique");
+        threads.into_iter().for_each(|t| t.join().unwrap());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "other owners are alive")]
+    fn test_assume_unique_misuse() {
+        let [p1, _p2] = StaticArc::new_const::<2>(5);
+        // SAFETY: not actually safe, but caught by the debug assertion
+        unsafe { p1.assume_unique() };
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, not(feature = "checked_raw")))]
+    #[should_panic(expected = "owner dropped with a count of 0")]
+    fn test_underflow() {
+        let [p1] = StaticArc::new_const::<1>(5);
+
+        // the weak reference keeps the allocation alive past the
+        // drop of the last real owner, so the forged one can run
+        let _weak = p1.downgrade();
+
+        // SAFETY: not actually safe, as this forges an extra owner
+        let forged = unsafe { StaticArc::from_raw(p1.as_ptr()) };
+        drop(p1);
+        drop(forged);
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "std", feature = "async"))]
+    #[cfg_attr(miri, ignore = "tokio leaks its runtime under miri")]
+    async fn test_poll_unique_broadcast() {
+        use std::time::Duration;
+
+        let [p1, p2] = StaticArc::new_const::<2>(5);
+        let p1 = Arc::new(p1);
+        let tasks: Vec<_> = (0..3)
+            .map(|_| {
+                let p1 = Arc::clone(&p1);
+                tokio::spawn(async move {
+                    core::future::poll_fn(|cx| StaticArc::poll_unique(&p1, cx)).await;
+                })
+            })
+            .collect();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop(p2);
+        });
+
+        for task in tasks {
+            tokio::time::timeout(Duration::from_secs(10), task)
+                .await
+                .expect("a waiting task wasn't woken")
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_from_box_trait() {
+        let boxed: Box<[i32]> = Box::new([1, 2, 3]);
+        let arc = StaticArc::from(boxed);
+        assert_eq!(arc.live().get(), 1);
+        assert_eq!(*arc, [1, 2, 3]);
+
+        let arc = StaticArc::from(Box::new(String::from("box")));
+        let [p1, p2] = arc.resplit::<2>().unwrap();
+        let p1 = p1.into_box().unwrap_err();
+        drop(p2);
+        assert_eq!(*p1.into_box().unwrap(), "box");
+    }
+
+    #[test]
+    fn test_weak_count() {
+        let dropped = Cell::new(false);
+        struct SetOnDrop<'a>(&'a Cell<bool>);
+
+        impl Drop for SetOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        let [p1, p2] = StaticArc::new_const::<2>(SetOnDrop(&dropped));
+        assert_eq!(StaticArc::weak_count(&p1), 0);
+
+        let w1 = p1.downgrade();
+        let w2 = p2.downgrade();
+        assert_eq!(StaticArc::weak_count(&p2), 2);
+        drop(w2);
+        assert_eq!(StaticArc::weak_count(&p1), 1);
+
+        drop((p1, p2));
+        assert!(dropped.get(), "the value outlived its owners");
+        assert!(w1.upgrade().is_none());
+        assert_eq!(w1.live(), 0);
+        drop(w1);
+    }
+
+    #[test]
+    fn test_inner_ptr() {
+        let [p1, p2] = StaticArc::new_const::<2>(5);
+        let [q1] = StaticArc::new_const::<1>(5);
+
+        assert_eq!(p1.inner_ptr(), p2.inner_ptr());
+        assert_ne!(p1.inner_ptr(), q1.inner_ptr());
+        assert_eq!(p1.live().get(), 2);
+    }
+
+    #[test]
+    fn test_addr() {
+        let [p1, p2] = StaticArc::new_const::<2>(5);
+        let [q1] = StaticArc::new_const::<1>(5);
+        let addr = p1.addr();
+        assert_eq!(addr, p2.addr());
+        assert_ne!(addr, q1.addr());
+
+        let moved = Box::new(p1);
+        assert_eq!(moved.addr(), addr);
+        let moved = [*moved];
+        assert_eq!(moved[0].addr(), addr);
+    }
+
+    #[test]
+    fn test_reclaim_error() {
+        let [p1, p2, p3] = StaticArc::new_const::<3>(5);
+        let err = p1.try_into_inner_err().unwrap_err();
+        assert_eq!(err.live(), 3);
+        assert_eq!(std::format!("{err}"), "cannot reclaim: 3 owners still live");
+
+        fn propagate(owner: StaticArc<i32>) -> Result<i32, std::boxed::Box<dyn std::error::Error>> {
+            Ok(owner.try_into_inner_err()?)
+        }
+
+        let p1 = err.into_owner();
+        drop(p2);
+        assert!(propagate(p3).is_err());
+        assert_eq!(p1.try_into_inner_err().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_new_default() {
+        let owners = StaticArc::<Vec<i32>>::new_default::<3>().unwrap();
+        assert!(owners.iter().all(|owner| owner.is_empty()));
+        assert!(StaticArc::<Vec<i32>>::new_default::<0>().is_none());
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "crossbeam"))]
+    fn test_into_inner_backoff() {
+        let [main, o1, o2, o3] = StaticArc::new_const::<4>(Mutex::new(0));
+        for owner in [o1, o2, o3] {
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    *owner.lock().unwrap() += 1;
+                }
+            });
+        }
+
+        assert_eq!(main.into_inner_backoff().into_inner().unwrap(), 300);
+    }
+
+    #[test]
+    fn test_new_aligned() {
+        let [p1, p2] = StaticArc::new_aligned::<2, 64>([1_u8; 3]).unwrap();
+        assert_eq!(&**p1 as *const [u8; 3] as usize % 64, 0);
+        assert_eq!(**p2, [1, 1, 1]);
+
+        drop(p2);
+        assert_eq!(p1.try_into_inner().unwrap().into_inner(), [1, 1, 1]);
+    }
+
+    #[test]
+    fn test_new_groups() {
+        let mut groups = StaticArc::new_groups::<2>(String::from("group"), 3);
+        assert_eq!(groups.len(), 3);
+        assert!(!StaticArc::ptr_eq(&groups[0][0], &groups[1][0]));
+
+        let [p1, p2] = groups.pop().unwrap();
+        drop(p2);
+        assert_eq!(p1.try_into_inner().unwrap(), "group");
+        assert!(groups.iter().all(|[g1, _]| g1.live().get() == 2));
+
+        assert!(StaticArc::new_groups::<2>(5, 0).is_empty());
+    }
+
+    #[test]
+    fn test_is_penultimate() {
+        let mut owners = Vec::from(StaticArc::new_const::<4>(5));
+        let last = owners.remove(0);
+
+        // each owner checks right before being dropped
+        let observed = owners
+            .into_iter()
+            .rev()
+            .filter(StaticArc::is_penultimate)
+            .count();
+
+        assert_eq!(observed, 1);
+        assert!(StaticArc::is_unique(&last));
+    }
+
+    #[test]
+    fn test_try_map_recover() {
+        let parse = |s: String| s.parse::<i32>();
+
+        let [p1, p2] = StaticArc::new_const::<2>(String::from("12"));
+        let (p1, err) = StaticArc::try_map_recover::<_, _, 2, _>(p1, parse).unwrap_err();
+        assert!(err.is_none());
+        drop(p2);
+
+        let [n1, n2] = StaticArc::try_map_recover(p1, parse).unwrap();
+        assert_eq!((*n1, *n2), (12, 12));
+
+        let [p1] = StaticArc::new_const::<1>(String::from("nope"));
+        let ptr = p1.as_ptr();
+        let (p1, err) = StaticArc::try_map_recover::<_, _, 2, _>(p1, parse).unwrap_err();
+        assert!(err.is_some());
+        assert_eq!(p1.as_ptr(), ptr);
+        assert_eq!(p1.try_into_inner().unwrap(), "nope");
+    }
+
+    #[test]
+    #[cfg(feature = "debug_checks")]
+    #[should_panic(expected = "corrupted control block")]
+    fn test_debug_checks() {
+        let [p1] = StaticArc::new_const::<1>(5);
+        let (ptr, token) = p1.into_parts();
+
+        // SAFETY: no references into the header are alive
+        unsafe {
+            let offset = mem::offset_of!(StaticArcInner<i32, Global>, magic);
+            ptr.cast::<u8>().add(offset).cast::<usize>().write(0xdead);
+        }
+
+        // SAFETY: the parts came from `into_parts`
+        let p1: StaticArc<i32> = unsafe { StaticArc::from_parts(ptr, token) };
+        let _ = *p1;
+    }
+
+    #[test]
+    fn test_unwrap_unique() {
+        let [p1] = StaticArc::new_const::<1>(5);
+        assert_eq!(p1.unwrap_unique(), 5);
+        let [p1] = StaticArc::new_const::<1>(5);
+        assert_eq!(p1.expect_unique("shared"), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "the pool is still in use: 3 owners still live")]
+    fn test_expect_unique_shared() {
+        let [p1, _p2, _p3] = StaticArc::new_const::<3>(5);
+        p1.expect_unique("the pool is still in use");
+    }
+
+    #[test]
+    #[should_panic(expected = "on a shared owner: 2 owners still live")]
+    fn test_unwrap_unique_shared() {
+        let [p1, _p2] = StaticArc::new_const::<2>(5);
+        p1.unwrap_unique();
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_expect_unique_while_dropping() {
+        use std::panic::{self, AssertUnwindSafe};
+
+        for _ in 0..100 {
+            let [p1, p2] = StaticArc::new_const::<2>(5);
+            let dropping = std::thread::spawn(move || drop(p2));
+
+            // either the other owner was dropped in time, or the
+            // message reports the count which made reclaiming fail
+            let result = panic::catch_unwind(AssertUnwindSafe(|| p1.expect_unique("shared")));
+            if let Err(panic) = result {
+                assert_eq!(panic.downcast_ref::<String>().unwrap(), "shared: 2 owners still live");
+            }
+            dropping.join().unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_init_once() {
+        use std::sync::atomic::AtomicUsize;
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let owners = StaticArc::<String>::new_uninit::<4>();
+        let threads: Vec<_> = owners
+            .into_iter()
+            .map(|owner| {
+                let runs = Arc::clone(&runs);
+                std::thread::spawn(move || {
+                    let ran = StaticArc::init_once(&owner, || {
+                        runs.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+                        String::from("init")
+                    });
+
+                    // SAFETY: `init_once` returned, so the value is initialized
+                    let owner = unsafe { StaticArc::assume_init(owner) };
+                    assert_eq!(*owner, "init");
+                    ran
+                })
+            })
+            .collect();
+
+        let ran = threads.into_iter().map(|t| t.join().unwrap());
+        assert_eq!(ran.filter(|&ran| ran).count(), 1);
+        assert_eq!(runs.load(core::sync::atomic::Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_init_once_panic() {
+        let [p1] = StaticArc::<i32>::new_uninit::<1>();
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            StaticArc::init_once(&p1, || std::panic!("oops"))
+        }));
+        assert!(panicked.is_err());
+
+        assert!(StaticArc::init_once(&p1, || 5));
+        assert!(!StaticArc::init_once(&p1, || 6));
+        assert_eq!(*unsafe { StaticArc::assume_init(p1) }, 5);
+    }
+
+    #[test]
+    fn test_header_size() {
+        const WORD: usize = mem::size_of::<usize>();
+
+        // by default, the counter, original count, weak count, `init`
+        // state (padded to a word) and `on_last_drop`, while `Global`
+        // takes no space; each feature then adds its own fields
+        let expected = 5 * WORD;
+        #[cfg(feature = "cache_padded")]
+        let expected = expected - WORD + mem::size_of::<StrongCount>();
+        #[cfg(feature = "checked_raw")]
+        let expected = expected + WORD;
+        // while `counted`, with the `metrics` feature,
+        // fits in the padding after `init`
+        #[cfg(feature = "debug_checks")]
+        let expected = expected + WORD;
+        #[cfg(feature = "async")]
+        let expected = expected + mem::size_of::<Waiters>();
+        #[cfg(feature = "park")]
+        let expected = expected + WORD;
+
+        assert_eq!(mem::offset_of!(StaticArcInner<(), Global>, value), expected);
+
+        let align = mem::align_of::<StaticArcInner<usize, Global>>();
+        assert_eq!(
+            mem::size_of::<StaticArcInner<usize, Global>>(),
+            (expected + WORD).next_multiple_of(align)
+        );
+    }
+
+    #[test]
+    fn test_downgrade_all() {
+        let dropped = Cell::new(false);
+        struct SetOnDrop<'a>(&'a Cell<bool>);
+
+        impl Drop for SetOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        let [p1, p2, p3] = StaticArc::new_const::<3>(SetOnDrop(&dropped));
+        let w0 = p1.downgrade();
+        let weaks = StaticArc::downgrade_all([p1, p2, p3]);
+        assert!(dropped.get(), "the value outlived its owners");
+
+        for w in weaks.iter().chain([&w0]) {
+            assert_eq!(w.live(), 0);
+            assert_eq!(w.weak_count(), 4);
+            assert!(w.upgrade().is_none());
+        }
+
+        drop(weaks);
+        assert_eq!(w0.weak_count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "owners of different allocations")]
+    fn test_downgrade_all_different() {
+        let [p1] = StaticArc::new_const::<1>(1);
+        let [p2] = StaticArc::new_const::<1>(1);
+        StaticArc::downgrade_all([p1, p2]);
+    }
+
+    #[test]
+    fn test_from_static_inner() {
+        use core::sync::atomic::AtomicUsize;
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        struct CountDrop;
+
+        impl Drop for CountDrop {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        static INNER: StaticArcInner<CountDrop, StaticMemory> = StaticArcInner::new::<2>(CountDrop);
+
+        // SAFETY: `INNER` was created for two owners, which are only taken here
+        let [p1, p2] = unsafe { StaticArc::from_static_inner::<2>(&INNER) };
+        let w = p1.downgrade();
+        drop(p1);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+
+        // freeing static memory would be caught by miri
+        drop(p2);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+        assert!(w.upgrade().is_none());
+        drop(w);
+    }
+
+    #[test]
+    fn test_from_storage() {
+        let drops = Cell::new(0);
+        struct CountDrop<'a>(&'a Cell<usize>);
+
+        impl Drop for CountDrop<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let mut storage = MaybeUninit::uninit();
+        let [p1, p2, p3] = StaticArc::from_storage::<3>(&mut storage, CountDrop(&drops));
+        assert_eq!(p1.live().get(), 3);
+        drop((p1, p2));
+        assert_eq!(drops.get(), 0);
+
+        // freeing the stack buffer would be caught by miri
+        drop(p3);
+        assert_eq!(drops.get(), 1);
+
+        // the storage is no longer borrowed, and still ours
+        let [p1] = StaticArc::from_storage::<1>(&mut storage, CountDrop(&drops));
+        assert!(p1.try_into_inner().is_some());
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn test_lock_unique() {
+        let [mut p1, p2] = StaticArc::new(vec![1]).unwrap();
+        assert!(StaticArc::lock_unique(&mut p1).is_none());
+        drop(p2);
+
+        let mut guard = StaticArc::lock_unique(&mut p1).unwrap();
+        guard.push(2);
+        assert_eq!(*guard, [1, 2]);
+        drop(guard);
+
+        let w = p1.downgrade();
+        assert!(StaticArc::lock_unique(&mut p1).is_none());
+        drop(w);
+        assert_eq!(p1.try_into_inner().unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn test_array_eq() {
+        let a = StaticArc::new_const::<3>(String::from("x"));
+        let b = StaticArc::new_const::<3>(String::from("x"));
+        let c = StaticArc::new_const::<3>(String::from("y"));
+
+        assert!(StaticArc::array_eq(&a, &a));
+        assert!(StaticArc::array_eq(&a, &b));
+        assert!(!StaticArc::array_eq(&a, &c));
+    }
+
+    #[test]
+    fn test_original_count() {
+        let owners = StaticArc::new_dyn(5, 4).unwrap();
+        let mut owners = owners.into_iter();
+        let p1 = owners.next().unwrap();
+
+        for (dropped, owner) in owners.enumerate() {
+            assert_eq!(StaticArc::original_count(&p1), 4);
+            assert_eq!(p1.live().get(), 4 - dropped);
+            drop(owner);
+        }
+        assert_eq!(StaticArc::original_count(&p1), 4);
+        assert_eq!(p1.live().get(), 1);
+
+        let [p1, p2] = p1.resplit::<2>().unwrap();
+        assert_eq!(StaticArc::original_count(&p2), 2);
+        drop(p2);
+
+        let ([p1], [_, _]) = p1.split_at::<1, 2>().unwrap();
+        assert_eq!(StaticArc::original_count(&p1), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "park")]
+    fn test_into_inner_wait_stress() {
+        let rounds = if cfg!(miri) { 10 } else { 1000 };
+
+        for round in 0..rounds {
+            let [main, o1, o2, o3] = StaticArc::new(round).unwrap();
+            let handles: Vec<_> = [o1, o2, o3]
+                .into_iter()
+                .map(|owner| std::thread::spawn(move || drop(owner)))
+                .collect();
+
+            // a lost wake up would hang here
+            assert_eq!(main.into_inner_wait(), round);
+            for handle in handles {
+                handle.join().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_into_inner_recycle() {
+        let bump = Bump::new();
+        let [p1, p2] = StaticArc::new_in(String::from("first"), &bump).unwrap();
+        let addr = p1.addr();
+        drop(p2);
+
+        let (value, slot) = p1.into_inner_recycle().unwrap();
+        assert_eq!(value, "first");
+
+        let [q1, q2, q3] = StaticArc::new_in_slot::<3>(slot, String::from("second"));
+        assert_eq!(q1.addr(), addr);
+        assert_eq!(*q2, "second");
+        assert_eq!(q3.live().get(), 3);
+        assert_eq!(bump.allocs.get(), 1);
+
+        // shared owners can't be recycled
+        assert!(q1.into_inner_recycle().is_none());
+        let w = q2.downgrade();
+        drop(q2);
+        assert!(q3.into_inner_recycle().is_none());
+        assert!(w.upgrade().is_none());
+        drop(w);
+        assert_eq!(bump.deallocs.get(), 1);
+
+        // dropping a slot frees the allocation
+        let [p1] = StaticArc::new_in(0, &bump).unwrap();
+        let (_, slot) = p1.into_inner_recycle().unwrap();
+        drop(slot);
+        assert_eq!((bump.allocs.get(), bump.deallocs.get()), (2, 2));
+    }
+
+    #[test]
+    fn test_new_in_static_slot() {
+        static INNER: StaticArcInner<Vec<i32>, StaticMemory> = StaticArcInner::new::<1>(Vec::new());
+
+        // SAFETY: `INNER` was created for a single owner, which is only taken here
+        let [p1] = unsafe { StaticArc::from_static_inner::<1>(&INNER) };
+        let (value, slot) = p1.into_inner_recycle().unwrap();
+        assert!(value.is_empty());
+
+        // the header of the `static` is reset through shared references
+        let [q1, q2] = StaticArc::new_in_slot::<2>(slot, vec![1]);
+        assert_eq!(q1.addr(), core::ptr::addr_of!(INNER) as usize);
+        assert_eq!(q2.live().get(), 2);
+        drop(q1);
+        assert_eq!(q2.try_into_inner(), Some(vec![1]));
+    }
+
+    #[test]
+    fn test_pin_from() {
+        let [p1, p2] = StaticArc::new_const::<2>(5u32);
+        let pinned: Pin<StaticArc<u32>> = p1.into();
+        assert_eq!(*pinned, 5);
+
+        let p1 = Pin::into_inner(pinned);
+        assert!(StaticArc::ptr_eq(&p1, &p2));
+        drop(p2);
+        assert_eq!(p1.try_into_inner(), Some(5));
+    }
+
+    #[test]
+    #[cfg(feature = "checked_raw")]
+    #[should_panic(expected = "reconstructed more owners than were turned into raw pointers")]
+    fn test_checked_raw() {
+        let [p1, p2] = StaticArc::new_const::<2>(5);
+        let ptr = p1.into_raw();
+
+        // SAFETY: `ptr` was returned by `into_raw`
+        let p1 = unsafe { StaticArc::from_raw(ptr) };
+        drop(p2);
+
+        // SAFETY: not actually safe, as this forges an extra owner,
+        // but `p1` keeps the allocation alive for the check to run
+        let forged = unsafe { StaticArc::from_raw(ptr) };
+        drop((p1, forged));
+    }
+
+    #[test]
+    #[cfg(feature = "checked_raw")]
+    #[should_panic(expected = "reconstructed more owners than were turned into raw pointers")]
+    #[cfg_attr(miri, ignore = "the allocation is leaked on purpose")]
+    fn test_checked_raw_leak() {
+        let [p1, p2] = StaticArc::new_const::<2>(5);
+        StaticArc::leak(p1);
+        let ptr = p2.into_raw();
+
+        // SAFETY: `ptr` was returned by `into_raw`
+        let p2 = unsafe { StaticArc::from_raw(ptr) };
+
+        // SAFETY: not actually safe, as this forges an extra owner, which
+        // the leaked one mustn't make up for, but the check panics first
+        let forged = unsafe { StaticArc::from_raw(ptr) };
+        drop((p2, forged));
+    }
+}
+
+/// Kept apart from `tests`, since `traced_test` expands to code whose
+/// `panic!` is ambiguous under the glob import of the std prelude.
+#[cfg(all(test, not(loom), feature = "tracing"))]
+mod tracing_tests {
+    use crate::StaticArc;
+    use std::format;
+    use std::string::{String, ToString};
+
+    #[test]
+    #[tracing_test::traced_test]
+    fn test_tracing() {
+        let [p1, p2] = StaticArc::new_const::<2>(5_u32);
+        drop(p1);
+        drop(p2);
+        let [p1] = StaticArc::new_const::<1>(String::new());
+        drop(p1);
+
+        logs_assert(|lines: &[&str]| {
+            let created = lines.iter().filter(|line| line.contains("shared allocation created"));
+            let dropped = lines.iter().filter(|line| line.contains("last owner dropped"));
+            match (created.count(), dropped.count()) {
+                (2, 2) => Ok(()),
+                counts => Err(format!("unexpected event counts: {counts:?}")),
+            }
+        });
+        assert!(logs_contain("owners=2 ty=\"u32\""));
     }
 }
@@ -0,0 +1,39 @@
+//! Process-wide count of live allocations, behind the `metrics` feature.
+
+// not swapped out under loom, since the count isn't part of
+// the synchronization between owners
+#[cfg(not(feature = "portable-atomic"))]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "portable-atomic")]
+use portable_atomic::{AtomicUsize, Ordering};
+
+static LIVE_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of allocations shared by owners which are
+/// currently alive, across all types of values, e.g. to detect leaks
+/// in long-running services.
+///
+/// An allocation is counted from the moment it is created, however
+/// many owners it is split into, until it is freed, after its last
+/// owner and weak reference are gone. Control blocks which didn't come
+/// from an allocator, in `static`s or in storage borrowed with
+/// [`StaticArc::from_storage`](crate::StaticArc::from_storage), aren't
+/// counted, even once recycled.
+///
+/// Other threads may create or free allocations concurrently, so the
+/// count is only a snapshot.
+#[inline]
+pub fn live_allocations() -> usize {
+    LIVE_ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+#[inline]
+pub(crate) fn allocated() {
+    LIVE_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn freed() {
+    LIVE_ALLOCATIONS.fetch_sub(1, Ordering::Relaxed);
+}
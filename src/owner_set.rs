@@ -0,0 +1,74 @@
+use core::array;
+use core::fmt;
+
+use crate::{Allocator, Global, StaticArc};
+
+/// The owners of an allocation, to be handed out one at a time,
+/// e.g. to threads.
+///
+/// Owners left in the set when it's dropped are dropped as well.
+pub struct OwnerSet<T: ?Sized, const N: usize, A: Allocator = Global> {
+    owners: array::IntoIter<StaticArc<T, A>, N>,
+}
+
+impl<T: ?Sized, const N: usize, A: Allocator> OwnerSet<T, N, A> {
+    /// Wraps the owners returned by one of the constructors
+    /// of [`StaticArc`].
+    #[inline]
+    pub fn new(owners: [StaticArc<T, A>; N]) -> Self {
+        OwnerSet { owners: owners.into_iter() }
+    }
+
+    /// Returns the number of owners left in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.owners.len()
+    }
+
+    /// Returns `true` if every owner was taken out of the set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Takes an owner out of the set, or returns `None` if
+    /// none are left.
+    #[inline]
+    pub fn take_one(&mut self) -> Option<StaticArc<T, A>> {
+        self.owners.next()
+    }
+
+    /// Calls `f` once with each owner left in the set.
+    #[inline]
+    pub fn distribute<F>(self, f: F)
+    where
+        F: FnMut(StaticArc<T, A>),
+    {
+        self.owners.for_each(f);
+    }
+}
+
+impl<T: ?Sized, const N: usize, A: Allocator> From<[StaticArc<T, A>; N]> for OwnerSet<T, N, A> {
+    #[inline]
+    fn from(owners: [StaticArc<T, A>; N]) -> Self {
+        OwnerSet::new(owners)
+    }
+}
+
+impl<T: ?Sized, const N: usize, A: Allocator> IntoIterator for OwnerSet<T, N, A> {
+    type Item = StaticArc<T, A>;
+    type IntoIter = array::IntoIter<StaticArc<T, A>, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.owners
+    }
+}
+
+impl<T: ?Sized, const N: usize, A: Allocator> fmt::Debug for OwnerSet<T, N, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnerSet")
+            .field("len", &self.len())
+            .finish()
+    }
+}
@@ -0,0 +1,30 @@
+//! Padding keeping the counter of owners on its own cache line,
+//! behind the `cache_padded` feature.
+
+use core::ops::Deref;
+
+/// Aligns and pads a value to the size of a cache line, like
+/// `crossbeam_utils::CachePadded`; x86_64 and aarch64 prefetch
+/// cache lines in pairs, so 128 bytes are used there.
+///
+/// It is `pub` in a private module, as the default counter of the
+/// public `StaticArcInner`, but can't be named outside of the crate.
+#[cfg_attr(any(target_arch = "x86_64", target_arch = "aarch64"), repr(align(128)))]
+#[cfg_attr(not(any(target_arch = "x86_64", target_arch = "aarch64")), repr(align(64)))]
+pub struct CachePadded<T>(T);
+
+impl<T> CachePadded<T> {
+    #[inline]
+    pub(crate) const fn new(value: T) -> Self {
+        CachePadded(value)
+    }
+}
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
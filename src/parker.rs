@@ -0,0 +1,145 @@
+//! The thread parked in `StaticArc::into_inner_wait`, waiting for
+//! its owner to be left alone.
+
+use core::ptr;
+
+use alloc::sync::Arc;
+
+use crate::sync::{self, AtomicPtr, Ordering, Thread};
+
+/// A single slot for a parked thread, holding a reference to it which
+/// is handed over to the owner that unparks it, so a waiter may give up
+/// without waiting for that owner to be done with it.
+///
+/// Only one thread can usefully wait at a time, since any other
+/// waiter holds an owner itself; if several try anyway, all but
+/// the registered one spin.
+pub(crate) struct Parker {
+    thread: AtomicPtr<Thread>,
+}
+
+impl Parker {
+    #[cfg(not(loom))]
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Parker { thread: AtomicPtr::new(ptr::null_mut()) }
+    }
+
+    // loom's atomics can't be created in constant expressions
+    #[cfg(loom)]
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Parker { thread: AtomicPtr::new(ptr::null_mut()) }
+    }
+
+    /// Registers `waiter`, the current thread, to be unparked, unless
+    /// it still is since a previous call.
+    ///
+    /// Returns `false` if another thread is registered instead. Once
+    /// this returns `true`, re-checking the count can't miss the
+    /// unpark from the owner leaving a single one behind.
+    pub(crate) fn register(&self, waiter: &mut Waiter) -> bool {
+        let current = self.thread.load(Ordering::Relaxed);
+
+        if current.is_null() {
+            let registered = Arc::into_raw(Arc::clone(&waiter.thread)) as *mut Thread;
+
+            // release the reference to whoever unparks us
+            if self
+                .thread
+                .compare_exchange(current, registered, Ordering::Release, Ordering::Relaxed)
+                .is_err()
+            {
+                // SAFETY: the reference was never shared
+                drop(unsafe { Arc::from_raw(registered) });
+                return false;
+            }
+            waiter.registered = true;
+        } else if !waiter.is(current) {
+            return false;
+        }
+
+        // pairs with the fence in `unpark`: either the owner being
+        // dropped sees our registration, or we see its decrement
+        sync::fence(Ordering::SeqCst);
+        true
+    }
+
+    /// Unparks the registered thread, if any, after the count of
+    /// owners was decremented down to one.
+    pub(crate) fn unpark(&self) {
+        sync::fence(Ordering::SeqCst);
+
+        if self.thread.load(Ordering::Relaxed).is_null() {
+            return;
+        }
+
+        // acquire, to synchronize with the registration
+        let thread = self.thread.swap(ptr::null_mut(), Ordering::Acquire);
+        if !thread.is_null() {
+            // SAFETY: the reference was leaked in `register`,
+            // and we took it out of the slot
+            unsafe { Arc::from_raw(thread) }.unpark();
+        }
+    }
+
+    /// Takes back the registration of `waiter`, if it wasn't
+    /// already taken by an owner unparking it.
+    pub(crate) fn unregister(&self, waiter: &Waiter) {
+        if !waiter.registered {
+            return;
+        }
+
+        let registered = Arc::as_ptr(&waiter.thread) as *mut Thread;
+
+        let taken = self.thread.compare_exchange(
+            registered,
+            ptr::null_mut(),
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        );
+
+        if taken.is_ok() {
+            // SAFETY: the reference was leaked in `register`,
+            // and we took it back out of the slot
+            drop(unsafe { Arc::from_raw(registered) });
+        }
+    }
+
+    /// Drops the registered thread, if any, without unparking it.
+    pub(crate) fn clear(&self) {
+        let thread = self.thread.swap(ptr::null_mut(), Ordering::Acquire);
+        if !thread.is_null() {
+            // SAFETY: the reference was leaked in `register`,
+            // and we took it out of the slot
+            drop(unsafe { Arc::from_raw(thread) });
+        }
+    }
+}
+
+/// The current thread, waiting to be unparked through a [`Parker`].
+///
+/// It keeps a handle of its own while waiting, so that no other handle
+/// of a registered thread shares its address: the slot holds its handle
+/// only if it is still registered, even once an owner took the slot's
+/// reference to unpark it and let go of it.
+pub(crate) struct Waiter {
+    thread: Arc<Thread>,
+    // whether we registered ourselves before, after which we can't
+    // read a stale pointer out of the slot, left there before our
+    // handle was allocated, possibly at the same address
+    registered: bool,
+}
+
+impl Waiter {
+    #[inline]
+    pub(crate) fn current() -> Self {
+        Waiter { thread: Arc::new(sync::current()), registered: false }
+    }
+
+    /// Whether `thread`, read from the slot, is our registration.
+    #[inline]
+    fn is(&self, thread: *mut Thread) -> bool {
+        self.registered && ptr::eq(thread, Arc::as_ptr(&self.thread))
+    }
+}
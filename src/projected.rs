@@ -0,0 +1,77 @@
+use core::fmt;
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+use crate::{Allocator, Global, StaticArc};
+
+/// A view into part of a value shared by a [`StaticArc`], which keeps
+/// the whole allocation alive.
+///
+/// Returned by [`StaticArc::map`].
+pub struct StaticArcProjected<T: ?Sized, U: ?Sized, A: Allocator = Global> {
+    // never used, other than to keep the allocation alive
+    owner: StaticArc<T, A>,
+    value: NonNull<U>,
+}
+
+/// Like `&U`, a projection may be sent to another thread when `U: Sync`,
+/// on top of the requirements for sending its owner.
+unsafe impl<T, U, A> Send for StaticArcProjected<T, U, A>
+where
+    T: ?Sized,
+    U: ?Sized + Sync,
+    A: Allocator,
+    StaticArc<T, A>: Send,
+{}
+
+unsafe impl<T, U, A> Sync for StaticArcProjected<T, U, A>
+where
+    T: ?Sized,
+    U: ?Sized + Sync,
+    A: Allocator,
+    StaticArc<T, A>: Sync,
+{}
+
+impl<T: ?Sized, U: ?Sized, A: Allocator> StaticArcProjected<T, U, A> {
+    pub(crate) fn new<F>(owner: StaticArc<T, A>, f: F) -> Self
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let value = NonNull::from(f(&owner));
+        StaticArcProjected { owner, value }
+    }
+
+    /// Returns the owner of the allocation backing this projection.
+    #[inline]
+    pub fn owner(this: &Self) -> &StaticArc<T, A> {
+        &this.owner
+    }
+
+    /// Projects further into the borrowed value, keeping the
+    /// same owner.
+    #[inline]
+    pub fn map<V: ?Sized, F>(this: Self, f: F) -> StaticArcProjected<T, V, A>
+    where
+        F: FnOnce(&U) -> &V,
+    {
+        let value = NonNull::from(f(&this));
+        StaticArcProjected { owner: this.owner, value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, A: Allocator> Deref for StaticArcProjected<T, U, A> {
+    type Target = U;
+
+    #[inline]
+    fn deref(&self) -> &U {
+        // SAFETY: the pointer was borrowed from the value shared by
+        // `owner`, which is kept alive for as long as we are
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + fmt::Debug, A: Allocator> fmt::Debug for StaticArcProjected<T, U, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
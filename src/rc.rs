@@ -0,0 +1,171 @@
+use core::cell::{Cell, UnsafeCell};
+use core::fmt;
+use core::mem::{self, ManuallyDrop};
+use core::num::NonZeroUsize;
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+use allocator_api2::alloc::{handle_alloc_error, Layout};
+
+use crate::counter::Counter;
+use crate::{Allocator, Global, StaticArcInner};
+
+/// A single-threaded [`StaticArc`](crate::StaticArc), like `Rc`
+/// is to `Arc`: the counter of owners isn't atomic, so owners can't
+/// be sent to other threads.
+///
+/// ```compile_fail
+/// use static_arc::StaticRc;
+///
+/// fn assert_send<S: Send>() {}
+///
+/// assert_send::<StaticRc<i32>>();
+/// ```
+pub struct StaticRc<T> {
+    inner: NonNull<StaticRcInner<T>>,
+}
+
+/// The same control block as that of a [`StaticArc`](crate::StaticArc),
+/// with a counter of owners that isn't atomic.
+type StaticRcInner<T> = StaticArcInner<T, Global, Cell<usize>>;
+
+impl<T> StaticRc<T> {
+    #[inline]
+    pub fn new<const N: usize>(value: T) -> Option<[Self; N]> {
+        Self::new_recover(value).ok()
+    }
+
+    pub fn new_recover<const N: usize>(value: T) -> Result<[Self; N], T> {
+        if !crate::valid_owners(N) {
+            return Err(value);
+        }
+
+        let layout = Layout::new::<StaticRcInner<T>>();
+        let inner = match Global.allocate(layout) {
+            Ok(ptr) => ptr.cast::<StaticRcInner<T>>(),
+            Err(_) => handle_alloc_error(layout),
+        };
+
+        // SAFETY: `inner` points at a fresh allocation
+        // that fits a `StaticRcInner<T>`
+        unsafe {
+            StaticArcInner::init(inner.as_ptr(), N, Global);
+            core::ptr::addr_of_mut!((*inner.as_ptr()).value)
+                .write(UnsafeCell::new(ManuallyDrop::new(value)));
+        }
+
+        Ok([(); N].map(|()| StaticRc { inner }))
+    }
+
+    #[inline]
+    fn rc(&self) -> &StaticRcInner<T> {
+        // SAFETY: this `StaticRc` has already been initialized
+        unsafe { self.inner.as_ref() }
+    }
+
+    #[inline]
+    fn value_ptr(&self) -> *mut T {
+        StaticArcInner::value_ptr(self.inner)
+    }
+
+    #[inline]
+    pub fn live(&self) -> NonZeroUsize {
+        // SAFETY: if we own a reference to `StaticRc`, the value
+        // of the counter will always be greater than 0
+        unsafe { NonZeroUsize::new_unchecked(self.rc().counter.get()) }
+    }
+
+    /// Returns `true` if both owners share the same allocation.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.inner == other.inner
+    }
+
+    /// Returns a mutable reference to the shared value, if this
+    /// is the only owner left.
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        if this.rc().is_unique() {
+            // SAFETY: we are the only owner left, and we hold
+            // a unique borrow of it
+            Some(unsafe { &mut *this.value_ptr() })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn try_into_inner(self) -> Option<T> {
+        self.try_into_inner_recover().ok()
+    }
+
+    pub fn try_into_inner_recover(self) -> Result<T, Self> {
+        if self.rc().counter.try_reclaim().is_err() {
+            return Err(self);
+        }
+
+        let inner = self.inner;
+        mem::forget(self);
+
+        // SAFETY: we were the only owner left, and
+        // brought the counter to 0
+        Ok(unsafe { StaticArcInner::reclaim(inner) })
+    }
+}
+
+impl<T> Deref for StaticRc<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // SAFETY: the value is alive while owners are,
+        // and only borrowed mutably through a unique owner
+        unsafe { &*self.value_ptr() }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for StaticRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticRc")
+            .field("live", &self.live())
+            .field("value", &**self)
+            .finish()
+    }
+}
+
+impl<T> Drop for StaticRc<T> {
+    fn drop(&mut self) {
+        // unless we were the last owner, we are done
+        if self.rc().counter.decrement() != 1 {
+            return;
+        }
+
+        // SAFETY: counter value reached 0, therefore
+        // no more `StaticRc` instances are alive
+        unsafe { StaticArcInner::drop_value(self.inner) };
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use std::prelude::rust_2021::*;
+
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let [p1, p2, p3] = StaticRc::new(String::from("rc")).unwrap();
+        assert_eq!(*p1, "rc");
+        assert!(StaticRc::ptr_eq(&p1, &p3));
+        drop(p2);
+        assert_eq!(p1.live().get(), 2);
+        assert!(StaticRc::<()>::new::<0>(()).is_none());
+        assert_eq!(StaticRc::new_recover::<0>(5).err(), Some(5));
+    }
+
+    #[test]
+    fn test_debug() {
+        let [p1, _p2] = StaticRc::new(1).unwrap();
+        assert_eq!(format!("{p1:?}"), "StaticRc { live: 2, value: 1 }");
+    }
+}
@@ -0,0 +1,135 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::mem::{self, ManuallyDrop};
+use core::ptr::NonNull;
+
+use crate::sync::Ordering;
+use crate::{assert_owners, Allocator, Global, StaticArc, StaticArcInner};
+
+/// An allocation left over by [`StaticArc::into_inner_recycle`], whose
+/// value was moved out, to be reused by [`StaticArc::new_in_slot`]
+/// without going through the allocator again, e.g. in object pools.
+///
+/// Dropping the slot frees the allocation.
+pub struct AllocSlot<T, A: Allocator = Global> {
+    inner: NonNull<StaticArcInner<T, A>>,
+}
+
+// SAFETY: the slot holds no value, only the allocator,
+// which frees the allocation on whichever thread drops it
+unsafe impl<T, A: Allocator + Send> Send for AllocSlot<T, A> {}
+unsafe impl<T, A: Allocator + Sync> Sync for AllocSlot<T, A> {}
+
+impl<T, A: Allocator> StaticArc<T, A> {
+    /// Reclaims the shared value like [`StaticArc::try_into_inner`],
+    /// but keeps the allocation around for reuse:
+    ///
+    /// ```
+    /// use static_arc::StaticArc;
+    ///
+    /// let [p1] = StaticArc::new_const::<1>(vec![1, 2, 3]);
+    /// let (value, slot) = p1.into_inner_recycle().unwrap();
+    /// assert_eq!(value, [1, 2, 3]);
+    ///
+    /// let [p1, p2] = StaticArc::new_in_slot::<2>(slot, vec![4, 5]);
+    /// assert_eq!(*p1, *p2);
+    /// ```
+    ///
+    /// Returns `None`, dropping this owner, unless it's the only one
+    /// left and no weak references exist, since those would keep the
+    /// allocation alive.
+    #[must_use = "ignoring the result drops the value, if it was reclaimed"]
+    pub fn into_inner_recycle(self) -> Option<(T, AllocSlot<T, A>)> {
+        // no weak references may be created from here on,
+        // since we hold the only owner
+        if !Self::is_unique(&self) {
+            return None;
+        }
+
+        let inner = self.inner;
+        // SAFETY: we are the only owner left, and no weak references
+        // exist, so nobody else can access the allocation
+        let value = unsafe {
+            self.arc().counter.store(0, Ordering::Relaxed);
+            core::ptr::read(self.value_ptr())
+        };
+
+        // the value isn't being dropped, and the slot
+        // starts afresh when reused
+        let arc = self.arc();
+        drop(arc.take_on_last_drop());
+        #[cfg(feature = "async")]
+        arc.waiters.clear();
+        #[cfg(feature = "park")]
+        arc.parker.clear();
+        mem::forget(self);
+
+        Some((value, AllocSlot { inner }))
+    }
+
+    /// Moves `value` into an allocation recycled by
+    /// [`StaticArc::into_inner_recycle`], returning its `N` owners,
+    /// without going through the allocator.
+    ///
+    /// Like [`StaticArc::new_const`], this doesn't compile with `N == 0`:
+    ///
+    /// ```compile_fail
+    /// use static_arc::StaticArc;
+    ///
+    /// let [p1] = StaticArc::new_const::<1>(5);
+    /// let (_, slot) = p1.into_inner_recycle().unwrap();
+    /// let _ = StaticArc::new_in_slot::<0>(slot, 6);
+    /// ```
+    pub fn new_in_slot<const N: usize>(slot: AllocSlot<T, A>, value: T) -> [Self; N] {
+        assert_owners::<N>();
+
+        let inner = slot.inner;
+        mem::forget(slot);
+
+        // SAFETY: the slot owns the allocation, whose value was moved
+        // out, so nobody else accesses it while we reset its header
+        unsafe {
+            inner.as_ref().reset(N);
+            UnsafeCell::raw_get(core::ptr::addr_of!((*inner.as_ptr()).value))
+                .write(ManuallyDrop::new(value));
+            Self::from_inner(inner)
+        }
+    }
+}
+
+impl<T, A: Allocator> StaticArcInner<T, A> {
+    /// Resets the header of a recycled allocation for `count` owners,
+    /// like [`StaticArcInner::init`] but through its atomics, since
+    /// control blocks in `static`s may only be accessed through shared
+    /// references; its allocator is left alone, and so is whether it
+    /// counts towards `live_allocations`, which it still does if it did.
+    fn reset(&self, count: usize) {
+        self.counter.store(count, Ordering::Relaxed);
+        self.original.store(count, Ordering::Relaxed);
+        #[cfg(feature = "checked_raw")]
+        self.raw.store(0, Ordering::Relaxed);
+        self.weak.store(1, Ordering::Relaxed);
+        self.init.store(crate::INIT_NONE, Ordering::Relaxed);
+
+        crate::trace!(
+            owners = count,
+            ty = core::any::type_name::<T>(),
+            addr = ?core::ptr::from_ref(self).cast::<()>(),
+            "shared allocation recycled",
+        );
+    }
+}
+
+impl<T, A: Allocator> Drop for AllocSlot<T, A> {
+    fn drop(&mut self) {
+        // SAFETY: the value was moved out, and the
+        // slot owns the allocation
+        unsafe { StaticArcInner::deallocate(self.inner) };
+    }
+}
+
+impl<T, A: Allocator> fmt::Debug for AllocSlot<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllocSlot").finish_non_exhaustive()
+    }
+}
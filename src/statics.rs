@@ -0,0 +1,114 @@
+//! Control blocks placed in static memory, rather than allocated.
+
+#[cfg(not(loom))]
+use core::cell::UnsafeCell;
+#[cfg(not(loom))]
+use core::mem::ManuallyDrop;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+use allocator_api2::alloc::Layout;
+
+#[cfg(not(loom))]
+use crate::sync::{AtomicPtr, AtomicU8, AtomicUsize};
+use crate::{assert_owners, AllocError, Allocator, StaticArc, StaticArcInner};
+
+/// An "allocator" for control blocks in static memory, built with
+/// [`StaticArcInner::new`], which never allocates nor frees anything.
+///
+/// Once the value of such a control block is dropped, e.g. along with
+/// its last owner, the control block is spent, but its memory is
+/// left alone.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StaticMemory;
+
+// SAFETY: no memory is ever handed out
+unsafe impl Allocator for StaticMemory {
+    #[inline]
+    fn allocate(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        Err(AllocError)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+}
+
+// loom's atomics can't be created in constant expressions
+#[cfg(not(loom))]
+impl<T> StaticArcInner<T, StaticMemory> {
+    /// Creates a control block for `N` owners of `value`, to be placed in
+    /// a `static` and shared with [`StaticArc::from_static_inner`].
+    ///
+    /// Like with [`StaticArc::new_const`], `N` must not be zero.
+    pub const fn new<const N: usize>(value: T) -> Self {
+        assert_owners::<N>();
+
+        StaticArcInner {
+            #[cfg(feature = "cache_padded")]
+            counter: crate::padded::CachePadded::new(AtomicUsize::new(N)),
+            #[cfg(not(feature = "cache_padded"))]
+            counter: AtomicUsize::new(N),
+            original: AtomicUsize::new(N),
+            #[cfg(feature = "checked_raw")]
+            raw: AtomicUsize::new(0),
+            weak: AtomicUsize::new(1),
+            init: AtomicU8::new(crate::INIT_NONE),
+            #[cfg(feature = "metrics")]
+            counted: false,
+            #[cfg(feature = "debug_checks")]
+            magic: AtomicUsize::new(crate::MAGIC),
+            #[cfg(feature = "async")]
+            waiters: crate::Waiters::new(),
+            #[cfg(feature = "park")]
+            parker: crate::Parker::new(),
+            on_last_drop: AtomicPtr::new(core::ptr::null_mut()),
+            alloc: StaticMemory,
+            value: UnsafeCell::new(ManuallyDrop::new(value)),
+        }
+    }
+}
+
+impl<T: ?Sized> StaticArc<T, StaticMemory> {
+    /// Returns the `N` owners of a control block in static memory,
+    /// without allocating, so they may be built in constant expressions:
+    ///
+    /// ```
+    /// use static_arc::{StaticArc, StaticArcInner, StaticMemory};
+    ///
+    /// static CONFIG: StaticArcInner<&str, StaticMemory> = StaticArcInner::new::<2>("config");
+    ///
+    /// // SAFETY: `CONFIG` was created for two owners, which are only taken here
+    /// static SHARED: [StaticArc<&str, StaticMemory>; 2] =
+    ///     unsafe { StaticArc::from_static_inner(&CONFIG) };
+    ///
+    /// assert_eq!(*SHARED[0], "config");
+    /// assert_eq!(SHARED[1].live().get(), 2);
+    /// ```
+    ///
+    /// Dropping the last owner drops the value, but never tries
+    /// to free the control block.
+    ///
+    /// # Safety
+    ///
+    /// `inner` must have been created with [`StaticArcInner::new`] for
+    /// `N` owners, and its owners may only be taken once.
+    pub const unsafe fn from_static_inner<const N: usize>(
+        inner: &'static StaticArcInner<T, StaticMemory>,
+    ) -> [Self; N] {
+        assert_owners::<N>();
+
+        // SAFETY: references are never null
+        let inner = unsafe { NonNull::new_unchecked(core::ptr::from_ref(inner).cast_mut()) };
+
+        let mut owners = [const { MaybeUninit::<Self>::uninit() }; N];
+        let mut i = 0;
+        while i < N {
+            owners[i] = MaybeUninit::new(StaticArc { inner });
+            i += 1;
+        }
+
+        // SAFETY: every owner was initialized, and `MaybeUninit<Self>`
+        // has the same layout as `Self`
+        unsafe { core::ptr::read(owners.as_ptr().cast::<[Self; N]>()) }
+    }
+}
@@ -0,0 +1,40 @@
+//! Atomics used by the crate, which are swapped out for the ones in
+//! [`loom`](https://docs.rs/loom) when building with `--cfg loom`, or
+//! for the ones in [`portable-atomic`](https://docs.rs/portable-atomic)
+//! with the `portable-atomic` feature.
+
+// `AtomicBool` is only used by the wakers of the `async` feature
+#[cfg(loom)]
+#[cfg_attr(not(feature = "async"), allow(unused_imports))]
+pub(crate) use loom::{
+    hint::spin_loop,
+    sync::atomic::{fence, AtomicBool, AtomicPtr, AtomicU8, AtomicUsize, Ordering},
+    thread::yield_now,
+};
+
+#[cfg(not(loom))]
+pub(crate) use core::hint::spin_loop;
+
+#[cfg(all(not(loom), not(feature = "portable-atomic")))]
+#[cfg_attr(not(feature = "async"), allow(unused_imports))]
+pub(crate) use core::sync::atomic::{fence, AtomicBool, AtomicPtr, AtomicU8, AtomicUsize, Ordering};
+
+#[cfg(all(not(loom), feature = "portable-atomic"))]
+#[cfg_attr(not(feature = "async"), allow(unused_imports))]
+pub(crate) use portable_atomic::{fence, AtomicBool, AtomicPtr, AtomicU8, AtomicUsize, Ordering};
+
+#[cfg(all(not(loom), feature = "std"))]
+pub(crate) use std::thread::yield_now;
+
+#[cfg(all(loom, feature = "park"))]
+pub(crate) use loom::thread::{current, park, Thread};
+
+#[cfg(all(not(loom), feature = "park"))]
+pub(crate) use std::thread::{current, park, Thread};
+
+/// Without a scheduler to yield to, keep spinning.
+#[cfg(all(not(loom), not(feature = "std")))]
+#[inline]
+pub(crate) fn yield_now() {
+    spin_loop();
+}
@@ -0,0 +1,16 @@
+//! Events emitted with the `tracing` feature, which compile to
+//! nothing without it.
+
+#[cfg(feature = "tracing")]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        ::tracing::trace!($($arg)*)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace {
+    ($($arg:tt)*) => {};
+}
+
+pub(crate) use trace;
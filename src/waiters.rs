@@ -0,0 +1,166 @@
+//! The wakers of the tasks waiting for an owner to be left alone.
+
+use core::cell::UnsafeCell;
+use core::mem;
+use core::task::Waker;
+
+use alloc::vec::Vec;
+
+use crate::backoff::Backoff;
+use crate::sync::{AtomicBool, Ordering};
+
+/// A set of wakers, all of which are woken at once, so that several
+/// tasks may wait on the same allocation without missing wake ups,
+/// unlike with a single `AtomicWaker` slot.
+///
+/// The set is guarded by a spin lock, which is only ever held while
+/// pushing or taking out wakers.
+///
+/// Every waker is stored under a key, handed back to whoever registered
+/// it, so that polling again replaces it, rather than adding another one,
+/// and a future dropped before completion can take its own waker out.
+pub(crate) struct Waiters {
+    locked: AtomicBool,
+    entries: UnsafeCell<Entries>,
+}
+
+struct Entries {
+    next: usize,
+    wakers: Vec<(usize, Waker)>,
+}
+
+impl Entries {
+    const fn new() -> Self {
+        Entries { next: 0, wakers: Vec::new() }
+    }
+}
+
+// SAFETY: the wakers are only accessed with the lock held
+unsafe impl Send for Waiters {}
+unsafe impl Sync for Waiters {}
+
+/// Releases the lock of [`Waiters`] when dropped.
+struct Guard<'a> {
+    waiters: &'a Waiters,
+}
+
+impl Drop for Guard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.waiters.locked.store(false, Ordering::Release);
+    }
+}
+
+impl Waiters {
+    #[cfg(not(loom))]
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Waiters {
+            locked: AtomicBool::new(false),
+            entries: UnsafeCell::new(Entries::new()),
+        }
+    }
+
+    // loom's atomics can't be created in constant expressions
+    #[cfg(loom)]
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Waiters {
+            locked: AtomicBool::new(false),
+            entries: UnsafeCell::new(Entries::new()),
+        }
+    }
+
+    /// Adds `waker` to the set, returning its key, or replaces the waker
+    /// stored under `key`, if it's still there.
+    ///
+    /// Without a key, this replaces a waker which would wake the same
+    /// task instead, if any.
+    ///
+    /// Taking the lock orders this against [`Waiters::wake_all`], so a
+    /// re-check of the count after registering can't miss a wake up.
+    pub(crate) fn register(&self, key: Option<usize>, waker: &Waker) -> usize {
+        // a replaced waker is dropped outside of the lock, as dropping
+        // it may drop a future, which would take the lock again
+        let (key, _replaced) = {
+            let _guard = self.lock();
+
+            // SAFETY: we hold the lock
+            let entries = unsafe { &mut *self.entries.get() };
+            let found = entries.wakers.iter_mut().find(|(k, w)| match key {
+                Some(key) => *k == key,
+                None => w.will_wake(waker),
+            });
+
+            match found {
+                Some((k, w)) if w.will_wake(waker) => (*k, None),
+                Some((k, w)) => (*k, Some(mem::replace(w, waker.clone()))),
+                None => {
+                    // a key taken out by `wake_all` may be stored again
+                    let k = key.unwrap_or_else(|| {
+                        entries.next = entries.next.wrapping_add(1);
+                        entries.next
+                    });
+                    entries.wakers.push((k, waker.clone()));
+                    (k, None)
+                },
+            }
+        };
+        key
+    }
+
+    /// Removes the waker stored under `key`, if it hasn't been woken yet.
+    pub(crate) fn deregister(&self, key: usize) {
+        let _removed = {
+            let _guard = self.lock();
+
+            // SAFETY: we hold the lock
+            let wakers = unsafe { &mut (*self.entries.get()).wakers };
+            wakers
+                .iter()
+                .position(|(k, _)| *k == key)
+                .map(|i| wakers.swap_remove(i))
+        };
+    }
+
+    /// Wakes up and removes every waker in the set; tasks that are
+    /// still waiting register again when polled.
+    pub(crate) fn wake_all(&self) {
+        let wakers = {
+            let _guard = self.lock();
+
+            // SAFETY: we hold the lock
+            mem::take(unsafe { &mut (*self.entries.get()).wakers })
+        };
+
+        // wake outside of the lock, as waking may take a while
+        for (_, waker) in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Drops every waker in the set without waking it, and frees the set,
+    /// so that it doesn't need to be dropped in place afterwards, which
+    /// couldn't be done through a shared reference into static memory.
+    pub(crate) fn clear(&self) {
+        let wakers = {
+            let _guard = self.lock();
+
+            // SAFETY: we hold the lock
+            mem::take(unsafe { &mut (*self.entries.get()).wakers })
+        };
+        drop(wakers);
+    }
+
+    fn lock(&self) -> Guard<'_> {
+        let mut backoff = Backoff::new();
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            backoff.snooze();
+        }
+        Guard { waiters: self }
+    }
+}
@@ -0,0 +1,121 @@
+use core::fmt;
+use core::panic::{RefUnwindSafe, UnwindSafe};
+use core::ptr::NonNull;
+
+use crate::sync::Ordering;
+use crate::{abort, Allocator, Global, StaticArc, StaticArcInner, MAX_REFCOUNT};
+
+/// A non-owning reference to the value shared by a set of [`StaticArc`]
+/// owners, obtained with [`StaticArc::downgrade`].
+///
+/// A `StaticWeak` doesn't keep the value alive, but it does keep the
+/// allocation around, so it can tell whether the value was dropped.
+pub struct StaticWeak<T: ?Sized, A: Allocator = Global> {
+    pub(crate) inner: NonNull<StaticArcInner<T, A>>,
+}
+
+// SAFETY: a `StaticWeak` can be upgraded into a `StaticArc`,
+// so it requires the same bounds
+unsafe impl<T: ?Sized + Send + Sync, A: Allocator + Send> Send for StaticWeak<T, A> {}
+unsafe impl<T: ?Sized + Send + Sync, A: Allocator + Send> Sync for StaticWeak<T, A> {}
+
+// like `StaticArc`
+impl<T: ?Sized + RefUnwindSafe, A: Allocator + UnwindSafe> UnwindSafe for StaticWeak<T, A> {}
+impl<T: ?Sized + RefUnwindSafe, A: Allocator + RefUnwindSafe> RefUnwindSafe for StaticWeak<T, A> {}
+
+impl<T: ?Sized, A: Allocator> StaticWeak<T, A> {
+    #[inline]
+    pub(crate) fn arc(&self) -> &StaticArcInner<T, A> {
+        // SAFETY: the allocation is kept alive while
+        // weak references to it exist
+        unsafe { self.inner.as_ref() }
+    }
+
+    /// Attempts to obtain an owner of the shared value, returning
+    /// `None` if all owners have been dropped.
+    ///
+    /// The returned owner behaves like any other: it counts towards
+    /// [`StaticArc::live`], and it must be dropped (or reclaim the
+    /// value) like the owners returned by [`StaticArc::new`]. In
+    /// particular, this means `live()` may temporarily exceed the
+    /// number of owners the value was originally split into, and that
+    /// an upgraded owner prevents its siblings from reclaiming the
+    /// value until it is dropped.
+    pub fn upgrade(&self) -> Option<StaticArc<T, A>> {
+        let counter = &self.arc().counter;
+        let mut current = counter.load(Ordering::Relaxed);
+
+        loop {
+            // never resurrect a dropped (or reclaimed) value
+            if current == 0 {
+                return None;
+            }
+
+            if current > MAX_REFCOUNT {
+                abort();
+            }
+
+            // acquire, to synchronize with the release decrements of
+            // dropped owners, like any other owner would have
+            match counter.compare_exchange_weak(
+                current,
+                current + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(StaticArc { inner: self.inner }),
+                Err(old) => current = old,
+            }
+        }
+    }
+
+    /// Returns the number of live owners, which is zero
+    /// if the value has been dropped.
+    #[inline]
+    pub fn live(&self) -> usize {
+        self.arc().counter.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of weak references to the allocation,
+    /// including this one.
+    ///
+    /// Like with `std::sync::Weak::weak_count`, the result may be off by
+    /// one while other threads drop the last owners of the value.
+    #[inline]
+    pub fn weak_count(&self) -> usize {
+        let weak = self.arc().weak.load(Ordering::Relaxed);
+
+        // the strong owners collectively hold one more,
+        // until the value is dropped
+        if self.arc().counter.load(Ordering::Relaxed) == 0 {
+            weak
+        } else {
+            weak - 1
+        }
+    }
+}
+
+/// Shows the number of live owners, for any `T`.
+impl<T: ?Sized, A: Allocator> fmt::Debug for StaticWeak<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticWeak")
+            .field("live", &self.live())
+            .finish()
+    }
+}
+
+#[cfg(feature = "coerce_unsized")]
+impl<T, U, A> core::ops::CoerceUnsized<StaticWeak<U, A>> for StaticWeak<T, A>
+where
+    T: ?Sized + core::marker::Unsize<U>,
+    U: ?Sized,
+    A: Allocator,
+{
+}
+
+impl<T: ?Sized, A: Allocator> Drop for StaticWeak<T, A> {
+    fn drop(&mut self) {
+        // SAFETY: we hold a weak reference, and give it up
+        unsafe { StaticArcInner::release_weak(self.inner) };
+    }
+}
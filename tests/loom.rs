@@ -0,0 +1,265 @@
+//! Models of the counter logic under the C11 memory model, run with:
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --test loom
+//! ```
+//!
+//! with `--features park` for the models of parked threads.
+
+#![cfg(loom)]
+
+#[cfg(feature = "park")]
+use std::time::Duration;
+
+use loom::cell::UnsafeCell;
+use loom::sync::atomic::{AtomicUsize, Ordering};
+use loom::sync::{mpsc, Arc};
+use loom::thread;
+
+use static_arc::{StaticArc, StaticWeak};
+
+// loom reports a causality violation if the value is
+// written while it may still be read by other owners
+struct Tracked {
+    data: UnsafeCell<usize>,
+    drops: Arc<AtomicUsize>,
+}
+
+// SAFETY: `data` is only written through `&mut Tracked`, or through
+// a shared owner while other owners don't access it
+unsafe impl Sync for Tracked {}
+
+impl Tracked {
+    fn new() -> (Self, Arc<AtomicUsize>) {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let data = UnsafeCell::new(1234);
+        (Tracked { data, drops: drops.clone() }, drops)
+    }
+
+    fn check(&self) {
+        assert_eq!(self.data.with(|data| unsafe { *data }), 1234);
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.data.with_mut(|data| unsafe { *data = 0 });
+        self.drops.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn drop_concurrently() {
+    loom::model(|| {
+        let (value, drops) = Tracked::new();
+        let owners: [_; 3] = StaticArc::new(value).unwrap();
+
+        let handles: Vec<_> = owners
+            .into_iter()
+            .map(|owner| {
+                thread::spawn(move || {
+                    owner.check();
+                    drop(owner);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // every use of the value happens before it is dropped,
+        // and it is dropped exactly once
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+    });
+}
+
+#[test]
+fn reclaim_while_dropping() {
+    loom::model(|| {
+        let (value, drops) = Tracked::new();
+        let [main, o1, o2] = StaticArc::new(value).unwrap();
+
+        let handles: Vec<_> = [o1, o2]
+            .into_iter()
+            .map(|owner| {
+                thread::spawn(move || {
+                    owner.check();
+                    drop(owner);
+                })
+            })
+            .collect();
+
+        match main.try_into_inner_recover() {
+            // the other owners are gone, and the value is ours
+            Ok(value) => {
+                value.check();
+                drop(value);
+            },
+            Err(main) => {
+                main.check();
+                drop(main);
+            },
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+    });
+}
+
+#[test]
+fn reclaim_sees_writes() {
+    struct Slot(UnsafeCell<usize>);
+
+    // SAFETY: the slot is written by a single owner, and only read
+    // back once the value is reclaimed
+    unsafe impl Sync for Slot {}
+
+    loom::model(|| {
+        let [main, writer] = StaticArc::new(Slot(UnsafeCell::new(0))).unwrap();
+
+        let handle = thread::spawn(move || {
+            writer.0.with_mut(|data| unsafe { *data = 42 });
+            drop(writer);
+        });
+
+        // the write happens before the writer is dropped,
+        // and so before the value can be reclaimed
+        let slot = main.into_inner_spin();
+        assert_eq!(slot.0.with(|data| unsafe { *data }), 42);
+
+        handle.join().unwrap();
+    });
+}
+
+#[test]
+fn reclaim_while_upgrading() {
+    loom::model(|| {
+        let (value, drops) = Tracked::new();
+        let [main] = StaticArc::new(value).unwrap();
+        let weak = main.downgrade();
+
+        let handle = thread::spawn(move || {
+            // either the upgrade happens first and keeps the value
+            // alive, or the value is already gone
+            if let Some(owner) = weak.upgrade() {
+                owner.check();
+                drop(owner);
+            }
+        });
+
+        match main.try_into_inner_recover() {
+            Ok(value) => {
+                value.check();
+                drop(value);
+            },
+            Err(main) => {
+                main.check();
+                drop(main);
+            },
+        }
+
+        handle.join().unwrap();
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+    });
+}
+
+#[test]
+fn get_mut_then_resplit() {
+    loom::model(|| {
+        let (value, _drops) = Tracked::new();
+        let [mut main, other] = StaticArc::new(value).unwrap();
+        let (sender, receiver) = mpsc::channel::<StaticWeak<Tracked>>();
+
+        // written through a shared owner, so that only the release of
+        // its drop and the acquire of `get_mut` order the write before
+        // anything the main thread does with the value, including
+        // handing it over below
+        let writing = thread::spawn(move || {
+            other.data.with_mut(|data| unsafe { *data = 5678 });
+            drop(other);
+        });
+
+        let upgrading = thread::spawn(move || {
+            let shared = receiver.recv().unwrap().upgrade().unwrap();
+            assert_eq!(shared.data.with(|data| unsafe { *data }), 5679);
+        });
+
+        let value = loop {
+            match StaticArc::get_mut(&mut main) {
+                Some(value) => break value,
+                None => thread::yield_now(),
+            }
+        };
+        value.data.with_mut(|data| unsafe { *data += 1 });
+
+        let [main, shared] = main.resplit::<2>().ok().unwrap();
+        sender.send(shared.downgrade()).unwrap();
+
+        writing.join().unwrap();
+        upgrading.join().unwrap();
+        drop((main, shared));
+    });
+}
+
+#[test]
+#[cfg(feature = "park")]
+fn park_while_dropping() {
+    loom::model(|| {
+        let (value, drops) = Tracked::new();
+        let [waiter, o1, o2] = StaticArc::new(value).unwrap();
+
+        // wait on another thread, since a late unpark would
+        // confuse loom's `join` on the main thread
+        let waiter = thread::spawn(move || {
+            // loom reports a deadlock if the waiter is never unparked
+            let value = waiter.into_inner_wait();
+            value.check();
+        });
+        let dropping = thread::spawn(move || {
+            o1.check();
+            drop(o1);
+        });
+
+        o2.check();
+        drop(o2);
+
+        dropping.join().unwrap();
+        waiter.join().unwrap();
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+    });
+}
+
+#[test]
+#[cfg(feature = "park")]
+fn park_with_two_waiters() {
+    loom::model(|| {
+        let (value, drops) = Tracked::new();
+        let [first, second, other] = StaticArc::new(value).unwrap();
+
+        // the first waiter may be unparked, and register again, while
+        // the second one takes and gives back the slot; it must still
+        // be unparked once the second one gives up its owner
+        let first = thread::spawn(move || {
+            let value = first.into_inner_wait();
+            value.check();
+        });
+        let second = thread::spawn(move || {
+            let Err(second) = second.into_inner_timeout(Duration::ZERO) else {
+                panic!("the first waiter still holds an owner");
+            };
+            second.check();
+            drop(second);
+        });
+
+        other.check();
+        drop(other);
+
+        second.join().unwrap();
+        first.join().unwrap();
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+    });
+}
@@ -0,0 +1,71 @@
+//! The count of live allocations is process-wide, so it is checked in
+//! its own test binary, where the tests take turns so that no other one
+//! allocates concurrently:
+//!
+//! ```text
+//! cargo test --features metrics --test metrics
+//! ```
+
+#![cfg(all(feature = "metrics", not(loom)))]
+
+use std::mem::MaybeUninit;
+use std::sync::Mutex;
+
+use static_arc::{live_allocations, StaticArc, StaticArcInner, StaticMemory};
+
+static SERIAL: Mutex<()> = Mutex::new(());
+
+#[test]
+fn live_allocations_return_to_baseline() {
+    let _serial = SERIAL.lock().unwrap();
+    let baseline = live_allocations();
+
+    let a: [_; 3] = StaticArc::new(1).unwrap();
+    let b: [_; 1] = StaticArc::new(String::from("b")).unwrap();
+    let c = StaticArc::new_dyn(vec![1, 2, 3], 4).unwrap();
+    assert_eq!(live_allocations(), baseline + 3, "counted per allocation");
+
+    // weak references keep the allocation alive
+    let w = a[0].downgrade();
+    drop(a);
+    assert_eq!(live_allocations(), baseline + 3);
+    drop(w);
+    assert_eq!(live_allocations(), baseline + 2);
+
+    drop((b, c));
+    assert_eq!(live_allocations(), baseline);
+}
+
+#[test]
+fn recycled_blocks_keep_their_count() {
+    static INNER: StaticArcInner<String, StaticMemory> = StaticArcInner::new::<1>(String::new());
+
+    let _serial = SERIAL.lock().unwrap();
+    let baseline = live_allocations();
+
+    // an allocation stays counted once, however many times it's recycled
+    let [p] = StaticArc::new_const::<1>(String::from("allocated"));
+    let (_, slot) = p.into_inner_recycle().unwrap();
+    assert_eq!(live_allocations(), baseline + 1);
+    let [p1, p2] = StaticArc::new_in_slot::<2>(slot, String::from("recycled"));
+    assert_eq!(live_allocations(), baseline + 1);
+    drop((p1, p2));
+    assert_eq!(live_allocations(), baseline);
+
+    // while a control block in static memory is never counted
+    //
+    // SAFETY: `INNER` was created for a single owner, which is only taken here
+    let [p] = unsafe { StaticArc::from_static_inner::<1>(&INNER) };
+    let (_, slot) = p.into_inner_recycle().unwrap();
+    let [p1, p2] = StaticArc::new_in_slot::<2>(slot, String::from("recycled"));
+    assert_eq!(live_allocations(), baseline);
+    drop((p1, p2));
+    assert_eq!(live_allocations(), baseline);
+
+    // nor is borrowed storage
+    let mut storage = MaybeUninit::uninit();
+    let [p1, p2] = StaticArc::from_storage::<2>(&mut storage, String::from("borrowed"));
+    assert_eq!(live_allocations(), baseline);
+    drop((p1, p2));
+    assert_eq!(live_allocations(), baseline);
+}